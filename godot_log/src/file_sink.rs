@@ -0,0 +1,211 @@
+use crate::Filter;
+use log::Record;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::SystemTime;
+
+/// Where log files go, before `user://` is resolved to a real directory.
+pub const DEFAULT_LOG_DIRECTORY: &str = "user://logs";
+
+/// A log file is rotated once it grows past this many bytes.
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// How many log files are kept, counting the one being written.
+pub const DEFAULT_MAX_FILES: usize = 5;
+
+const FILE_STEM: &str = "saltgui";
+const FILE_EXTENSION: &str = "log";
+
+/// Settings for writing log records to files.
+#[derive(Debug, Clone)]
+pub struct FileSinkConfig {
+    /// A real directory on disk; `user://` paths must be globalized first.
+    pub directory: PathBuf,
+    pub max_file_bytes: u64,
+    pub max_files: usize,
+    pub filter: Filter,
+}
+
+/// Writes timestamped records to `saltgui.log`, moving full files aside to
+/// `saltgui.1.log`, `saltgui.2.log` and so on, and deleting the oldest.
+pub(crate) struct FileSink {
+    config: FileSinkConfig,
+
+    /// The open `saltgui.log`, closed while rotating since Windows can't rename open files.
+    file: Option<File>,
+    written: u64,
+}
+
+impl FileSink {
+    pub fn open(config: FileSinkConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.directory)?;
+
+        let file = open_current(&config.directory)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            config,
+            file: Some(file),
+            written,
+        })
+    }
+
+    pub fn filter(&self) -> &Filter {
+        &self.config.filter
+    }
+
+    /// Writes a line from `format_line`, rotating first if the file would grow too big.
+    pub fn write(&mut self, line: &str) -> io::Result<()> {
+        if self.written > 0 && self.written + line.len() as u64 > self.config.max_file_bytes {
+            self.rotate()?;
+        }
+
+        // A failed rotation leaves no file open, so try again.
+        if self.file.is_none() {
+            self.file = Some(open_current(&self.config.directory)?);
+        }
+
+        let file = self.file.as_mut().expect("log file was just opened");
+        file.write_all(line.as_bytes())?;
+        self.written += line.len() as u64;
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), Write::flush)
+    }
+
+    /// Shifts every log file up by one, dropping those past the retention count,
+    /// and starts a fresh file.
+    fn rotate(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+
+        let directory = &self.config.directory;
+        let max_files = self.config.max_files.max(1);
+
+        let oldest = log_path(directory, max_files - 1);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+
+        for index in (0..max_files - 1).rev() {
+            let from = log_path(directory, index);
+            if from.exists() {
+                fs::rename(&from, log_path(directory, index + 1))?;
+            }
+        }
+
+        self.file = Some(open_current(directory)?);
+        self.written = 0;
+
+        Ok(())
+    }
+}
+
+/// A record as one timestamped line of a log file. `message` is the record's formatted
+/// arguments, formatted before the sink is locked since formatting them can panic.
+pub(crate) fn format_line(record: &Record, message: &str) -> String {
+    let current = thread::current();
+
+    format!(
+        "{} [{}][{}][{}][{}] {}\n",
+        humantime::format_rfc3339_millis(SystemTime::now()),
+        record.level(),
+        current.name().unwrap_or("unnamed"),
+        record.module_path().unwrap_or("??"),
+        record.line().unwrap_or(0),
+        message
+    )
+}
+
+fn open_current(directory: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path(directory, 0))
+}
+
+/// The path of the log file `index` rotations old; 0 is the file being written.
+fn log_path(directory: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        directory.join(format!("{}.{}", FILE_STEM, FILE_EXTENSION))
+    } else {
+        directory.join(format!("{}.{}.{}", FILE_STEM, index, FILE_EXTENSION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An empty directory of its own for each test.
+    fn temp_dir(test_name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("godot_log-{}-{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn open(directory: &Path, max_file_bytes: u64, max_files: usize) -> FileSink {
+        FileSink::open(FileSinkConfig {
+            directory: directory.to_path_buf(),
+            max_file_bytes,
+            max_files,
+            filter: Filter::new(),
+        })
+        .unwrap()
+    }
+
+    fn write(sink: &mut FileSink, message: &str) {
+        sink.write(&format!("{}\n", message)).unwrap();
+    }
+
+    fn read(directory: &Path, index: usize) -> String {
+        fs::read_to_string(log_path(directory, index)).unwrap()
+    }
+
+    #[test]
+    fn rotates_once_a_file_would_pass_max_file_bytes() {
+        let directory = temp_dir("rotates");
+
+        // Room for exactly two of the lines below.
+        let line_len = "record 1\n".len() as u64;
+        let mut sink = open(&directory, line_len * 2, 3);
+        write(&mut sink, "record 1");
+        write(&mut sink, "record 2");
+        assert!(!log_path(&directory, 1).exists());
+
+        write(&mut sink, "record 3");
+        drop(sink);
+
+        let rotated = read(&directory, 1);
+        assert!(rotated.contains("record 1") && rotated.contains("record 2"));
+        assert!(read(&directory, 0).contains("record 3"));
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn removes_files_past_max_files() {
+        let directory = temp_dir("retention");
+
+        // Every record after the first rotates.
+        let mut sink = open(&directory, 1, 3);
+        for index in 0..5 {
+            write(&mut sink, &format!("record {}", index));
+        }
+        drop(sink);
+
+        assert!(read(&directory, 0).contains("record 4"));
+        assert!(read(&directory, 1).contains("record 3"));
+        assert!(read(&directory, 2).contains("record 2"));
+        assert!(!log_path(&directory, 3).exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+use log::{LevelFilter, Metadata};
+
+/// One `module=level` part of a filter string, or a bare `level` for every module.
+#[derive(Debug, Clone, PartialEq)]
+struct Directive {
+    module: Option<String>,
+    level: LevelFilter,
+}
+
+/// Which records to log, parsed from an env_logger-style string such as
+/// `saltgui::agent=debug,info`: records from a module use the level of the longest
+/// directive naming it or a parent module, and everything else uses the bare level.
+/// As in env_logger, a later directive for the same module, or a later bare level,
+/// replaces an earlier one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    directives: Vec<Directive>,
+}
+
+impl Filter {
+    /// A filter logging everything at `Info` and above.
+    pub const fn new() -> Self {
+        Self {
+            directives: Vec::new(),
+        }
+    }
+
+    /// Parses a filter string. Parts that don't parse are skipped and returned as errors.
+    pub fn parse(spec: &str) -> (Self, Vec<String>) {
+        let mut directives = Vec::new();
+        let mut errors = Vec::new();
+
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let mut pieces = part.splitn(2, '=');
+            let first = pieces.next().unwrap_or_default();
+
+            let directive = match pieces.next() {
+                Some(level) => level.parse().ok().map(|level| Directive {
+                    module: Some(first.to_string()),
+                    level,
+                }),
+                // A bare word is either a level for everything, or a module to log in full.
+                None => Some(match first.parse() {
+                    Ok(level) => Directive {
+                        module: None,
+                        level,
+                    },
+                    Err(_) => Directive {
+                        module: Some(first.to_string()),
+                        level: LevelFilter::Trace,
+                    },
+                }),
+            };
+
+            match directive {
+                Some(directive) => directives.push(directive),
+                None => errors.push(part.to_string()),
+            }
+        }
+
+        let mut directives = directives
+            .into_iter()
+            .rev()
+            .fold(Vec::new(), |mut kept, d| {
+                if !kept.iter().any(|k: &Directive| k.module == d.module) {
+                    kept.push(d);
+                }
+                kept
+            });
+
+        // Most specific first, so the first match wins.
+        directives.sort_by_key(|d| std::cmp::Reverse(d.module.as_ref().map_or(0, String::len)));
+
+        (Self { directives }, errors)
+    }
+
+    /// The level for records from `target`, a module path like `saltgui::agent::world`.
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .find(|d| match &d.module {
+                Some(module) => is_module_or_child(target, module),
+                None => true,
+            })
+            .map_or(LevelFilter::Info, |d| d.level)
+    }
+
+    pub fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    /// The most verbose level any module is logged at.
+    pub fn max_level(&self) -> LevelFilter {
+        let has_bare_level = self.directives.iter().any(|d| d.module.is_none());
+        let unlisted_level = if has_bare_level {
+            None
+        } else {
+            Some(LevelFilter::Info)
+        };
+
+        self.directives
+            .iter()
+            .map(|d| d.level)
+            .chain(unlisted_level)
+            .max()
+            .unwrap_or(LevelFilter::Info)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_module_or_child(target: &str, module: &str) -> bool {
+    target == module || (target.starts_with(module) && target[module.len()..].starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(spec: &str) -> Filter {
+        let (filter, errors) = Filter::parse(spec);
+        assert!(errors.is_empty(), "{:?}", errors);
+        filter
+    }
+
+    #[test]
+    fn module_directive_overrides_bare_level() {
+        let filter = parse("warn,saltgui::agent=debug");
+
+        assert_eq!(
+            filter.level_for("saltgui::agent::world"),
+            LevelFilter::Debug
+        );
+        assert_eq!(filter.level_for("saltgui::board"), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn directives_match_child_modules_only() {
+        let filter = parse("saltgui::agent=trace");
+
+        assert_eq!(filter.level_for("saltgui::agent"), LevelFilter::Trace);
+        assert_eq!(
+            filter.level_for("saltgui::agent::world"),
+            LevelFilter::Trace
+        );
+        assert_eq!(filter.level_for("saltgui::agentx"), LevelFilter::Info);
+        assert_eq!(filter.level_for("saltgui"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn longest_module_wins() {
+        let filter = parse("saltgui::agent=debug,saltgui=error");
+
+        assert_eq!(
+            filter.level_for("saltgui::agent::world"),
+            LevelFilter::Debug
+        );
+        assert_eq!(filter.level_for("saltgui::board"), LevelFilter::Error);
+    }
+
+    #[test]
+    fn bare_module_logs_everything() {
+        assert_eq!(
+            parse("saltgui").level_for("saltgui::hand"),
+            LevelFilter::Trace
+        );
+    }
+
+    #[test]
+    fn last_directive_for_a_module_wins() {
+        let filter = parse("debug,warn,saltgui=trace,saltgui=error");
+
+        assert_eq!(filter.level_for("other"), LevelFilter::Warn);
+        assert_eq!(filter.level_for("saltgui"), LevelFilter::Error);
+        // The replaced `debug` and `trace` no longer count.
+        assert_eq!(filter.max_level(), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn invalid_directives_are_returned_as_errors() {
+        let (filter, errors) = Filter::parse("error, saltgui=loud,,hand=debug=trace ");
+
+        assert_eq!(errors, vec!["saltgui=loud", "hand=debug=trace"]);
+        assert_eq!(filter, parse("error"));
+    }
+
+    #[test]
+    fn max_level_covers_every_module() {
+        assert_eq!(parse("").max_level(), LevelFilter::Info);
+        assert_eq!(parse("error").max_level(), LevelFilter::Error);
+        assert_eq!(parse("error,saltgui=debug").max_level(), LevelFilter::Debug);
+        // Modules not listed still log at the default level.
+        assert_eq!(parse("saltgui=warn").max_level(), LevelFilter::Info);
+    }
+}
@@ -1,33 +1,344 @@
-use gdnative::godot_print;
-use log::{info, LevelFilter, Log};
+mod file_sink;
+mod filter;
+mod panic;
+mod recent;
+
+pub use file_sink::{
+    FileSinkConfig, DEFAULT_LOG_DIRECTORY, DEFAULT_MAX_FILES, DEFAULT_MAX_FILE_BYTES,
+};
+pub use filter::Filter;
+pub use panic::{take_panic_report, PanicReport};
+pub use recent::{recent_records, LogEntry, RECENT_CAPACITY};
+
+use crossbeam_queue::SegQueue;
+use file_sink::FileSink;
+use gdnative::api::ProjectSettings;
+use gdnative::godot_warn;
+use gdnative::log::Site;
+use log::{info, warn, Level, Log};
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::thread::{self, ThreadId};
+
+/// The environment variable holding the log filter, e.g. `saltgui::agent=debug,info`.
+pub const FILTER_ENV_VAR: &str = "SALTGUI_LOG";
+
+/// The project setting holding the log filter, used when the environment variable isn't set.
+pub const FILTER_PROJECT_SETTING: &str = "logging/saltgui/filter";
+
+/// The environment variable holding the file log filter; empty or unset means no log files.
+pub const FILE_FILTER_ENV_VAR: &str = "SALTGUI_FILE_LOG";
+
+/// The project setting holding the file log filter, used when the environment variable isn't set.
+pub const FILE_FILTER_PROJECT_SETTING: &str = "logging/saltgui/file_filter";
+
+/// How many records from other threads are kept until `flush_pending`; older ones are dropped.
+pub const PENDING_CAPACITY: usize = 1000;
+
+/// Which records are printed to the Godot console.
+static FILTER: RwLock<Filter> = RwLock::new(Filter::new());
+
+/// The string `FILTER` was parsed from, to show in a settings menu.
+static FILTER_SPEC: RwLock<String> = RwLock::new(String::new());
+
+/// Where records are also written to disk, if anywhere.
+static FILE_SINK: Mutex<Option<FileSink>> = Mutex::new(None);
+
+/// The thread the logger was initialized on, the only one allowed to call into Godot.
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+/// Records logged on other threads, waiting for `flush_pending` on the main thread.
+static PENDING: SegQueue<PendingRecord> = SegQueue::new();
+
+/// How many pending records were dropped since the last `flush_pending`.
+static DROPPED_PENDING: AtomicUsize = AtomicUsize::new(0);
+
+/// A record from another thread, formatted and kept until it can be sent to Godot.
+struct PendingRecord {
+    level: Level,
+    file: String,
+    module_path: String,
+    line: u32,
+    message: String,
+}
 
 pub struct GodotLog;
 
 impl GodotLog {
     pub fn init() {
+        MAIN_THREAD.get_or_init(|| thread::current().id());
+
         if log::set_boxed_logger(Box::new(Self)).is_ok() {
-            log::set_max_level(LevelFilter::Info);
+            Self::set_filter(&configured_filter(FILTER_ENV_VAR, FILTER_PROJECT_SETTING));
+
+            let file_spec = configured_filter(FILE_FILTER_ENV_VAR, FILE_FILTER_PROJECT_SETTING);
+            if let Err(e) = Self::set_file_filter(&file_spec) {
+                godot_warn!("Could not open the log file: {}", e);
+            }
+
+            panic::install_hook();
+
             info!("Godot logger initialized.");
         } else {
             info!("Logger already initialized.");
         }
     }
+
+    /// Replaces the console log filter, e.g. from a settings menu.
+    /// Parts of `spec` that don't parse are reported and skipped.
+    pub fn set_filter(spec: &str) {
+        if let Ok(mut current) = FILTER.write() {
+            *current = parse_filter(spec);
+        }
+
+        if let Ok(mut current) = FILTER_SPEC.write() {
+            *current = spec.to_string();
+        }
+
+        update_max_level();
+    }
+
+    /// The console log filter, as last passed to `set_filter`.
+    pub fn filter_spec() -> String {
+        FILTER_SPEC
+            .read()
+            .map(|spec| spec.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets the least severe level kept for `recent_records`, regardless of the console and
+    /// file filters. Only records logged from then on are affected.
+    pub fn set_recent_level(level: log::LevelFilter) {
+        recent::set_level(level);
+        update_max_level();
+    }
+
+    /// Writes records matching `spec` to rotating files in `user://logs`,
+    /// or stops writing log files if `spec` is empty. Must be invoked on the main thread.
+    pub fn set_file_filter(spec: &str) -> io::Result<()> {
+        if spec.trim().is_empty() {
+            Self::disable_file_sink();
+            return Ok(());
+        }
+
+        let directory = ProjectSettings::godot_singleton()
+            .globalize_path(DEFAULT_LOG_DIRECTORY)
+            .to_string();
+
+        Self::enable_file_sink(FileSinkConfig {
+            directory: directory.into(),
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+            filter: parse_filter(spec),
+        })
+    }
+
+    /// Starts writing records to files, replacing any previous file sink.
+    pub fn enable_file_sink(config: FileSinkConfig) -> io::Result<()> {
+        let sink = FileSink::open(config)?;
+
+        if let Ok(mut current) = FILE_SINK.lock() {
+            *current = Some(sink);
+        }
+
+        update_max_level();
+        Ok(())
+    }
+
+    pub fn disable_file_sink() {
+        if let Ok(mut current) = FILE_SINK.lock() {
+            if let Some(mut sink) = current.take() {
+                let _ = sink.flush();
+            }
+        }
+
+        update_max_level();
+    }
+
+    /// Sends records logged on other threads to Godot, oldest first, after a warning if any were
+    /// dropped. Invoke this from the main thread every frame.
+    pub fn flush_pending() {
+        if !is_main_thread() {
+            return;
+        }
+
+        let dropped = DROPPED_PENDING.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            let message = format!(
+                "{} records from other threads were dropped before they could be printed",
+                dropped
+            );
+            write(Level::Warn, file!(), module_path!(), line!(), &message);
+        }
+
+        while let Some(record) = PENDING.pop() {
+            write(
+                record.level,
+                &record.file,
+                &record.module_path,
+                record.line,
+                &record.message,
+            );
+        }
+    }
 }
 
 impl Log for GodotLog {
-    fn enabled(&self, _metadata: &log::Metadata) -> bool {
-        true
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        console_enabled(metadata) || file_enabled(metadata) || recent::enabled(metadata)
     }
 
     fn log(&self, record: &log::Record) {
-        godot_print!(
-            "[{}][{}][{}] {}",
-            record.level(),
-            record.module_path().unwrap_or("??"),
-            record.line().unwrap_or(0),
-            record.args()
-        );
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // Formatted once, before any lock is taken: a panicking `Display` impl runs the panic
+        // hook, which logs too, and would deadlock on a lock held here.
+        let message = record.args().to_string();
+
+        if recent::enabled(record.metadata()) {
+            recent::push(record, &message);
+        }
+
+        if file_enabled(record.metadata()) {
+            write_to_file(record, &message);
+        }
+
+        if !console_enabled(record.metadata()) {
+            return;
+        }
+
+        let file = record.file().unwrap_or("??");
+        let module_path = record.module_path().unwrap_or("??");
+        let line = record.line().unwrap_or(0);
+
+        if is_main_thread() {
+            write(record.level(), file, module_path, line, &message);
+        } else {
+            // Godot isn't safe to call from here, so the main thread writes it later.
+            let current = thread::current();
+            let thread_name = current.name().unwrap_or("unnamed");
+
+            PENDING.push(PendingRecord {
+                level: record.level(),
+                file: file.to_string(),
+                module_path: module_path.to_string(),
+                line,
+                message: format!("[thread {}] {}", thread_name, message),
+            });
+
+            // Nothing drains the queue until the main thread flushes it, so keep only the newest.
+            while PENDING.len() > PENDING_CAPACITY {
+                if PENDING.pop().is_some() {
+                    DROPPED_PENDING.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    fn flush(&self) {
+        Self::flush_pending();
+
+        if let Ok(mut sink) = FILE_SINK.lock() {
+            if let Some(sink) = sink.as_mut() {
+                let _ = sink.flush();
+            }
+        }
+    }
+}
+
+/// The filter string from the environment, or failing that the project settings.
+fn configured_filter(env_var: &str, project_setting: &str) -> String {
+    if let Ok(spec) = std::env::var(env_var) {
+        return spec;
+    }
+
+    let settings = ProjectSettings::godot_singleton();
+    if settings.has_setting(project_setting) {
+        return settings
+            .get_setting(project_setting)
+            .try_to_string()
+            .unwrap_or_default();
     }
 
-    fn flush(&self) {}
+    String::new()
+}
+
+/// Parses a filter string, reporting and skipping the parts that don't parse.
+fn parse_filter(spec: &str) -> Filter {
+    let (filter, errors) = Filter::parse(spec);
+
+    for error in errors {
+        godot_warn!("Ignoring invalid log filter directive: {}", error);
+    }
+
+    filter
+}
+
+/// Lets through the most verbose level either the console or the log files want.
+fn update_max_level() {
+    let console = FILTER
+        .read()
+        .map_or(log::LevelFilter::Info, |filter| filter.max_level());
+
+    let file = FILE_SINK
+        .lock()
+        .ok()
+        .and_then(|sink| sink.as_ref().map(|sink| sink.filter().max_level()))
+        .unwrap_or(log::LevelFilter::Off);
+
+    log::set_max_level(console.max(file).max(recent::max_level()));
+}
+
+fn console_enabled(metadata: &log::Metadata) -> bool {
+    FILTER
+        .read()
+        .map_or(true, |filter| filter.enabled(metadata))
+}
+
+fn file_enabled(metadata: &log::Metadata) -> bool {
+    FILE_SINK.lock().map_or(false, |sink| {
+        sink.as_ref().map_or(false, |sink| sink.filter().enabled(metadata))
+    })
+}
+
+/// Writes a record to the log file. If that fails, the file sink is closed,
+/// so a full disk doesn't turn every log call into an error.
+fn write_to_file(record: &log::Record, message: &str) {
+    let line = file_sink::format_line(record, message);
+
+    let error = match FILE_SINK.lock() {
+        Ok(mut current) => match current.as_mut().map(|sink| sink.write(&line)) {
+            Some(Err(e)) => {
+                *current = None;
+                e
+            }
+            _ => return,
+        },
+        Err(_) => return,
+    };
+
+    update_max_level();
+
+    // Logged after the lock is released, since logging takes it again.
+    warn!("Stopped writing log files: {}", error);
+}
+
+fn is_main_thread() -> bool {
+    MAIN_THREAD.get() == Some(&thread::current().id())
+}
+
+/// Sends a record to Godot's error, warning or print channel, depending on its level.
+fn write(level: Level, file: &str, module_path: &str, line: u32, message: &str) {
+    // Godot shows errors and warnings in its debugger, where the site links to the source.
+    let site = Site::new(file, module_path, line);
+    let message = format!("[{}][{}][{}] {}", level, module_path, line, message);
+
+    match level {
+        Level::Error => gdnative::log::error(site, message),
+        Level::Warn => gdnative::log::warn(site, message),
+        _ => gdnative::log::print(message),
+    }
 }
@@ -0,0 +1,70 @@
+use log::error;
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::sync::Mutex;
+use std::thread;
+
+/// What went wrong in a panic, for showing to the player.
+#[derive(Debug, Clone)]
+pub struct PanicReport {
+    pub message: String,
+
+    /// `file:line:column` of the panic, if known.
+    pub location: Option<String>,
+    pub thread: String,
+    pub backtrace: String,
+}
+
+/// The first panic, waiting for the main thread to show it.
+/// Later panics are usually fallout from the first, so they're only logged.
+static REPORT: Mutex<Option<PanicReport>> = Mutex::new(None);
+
+/// Logs every panic, with its backtrace, before Rust's default handling,
+/// and keeps the first for `take_panic_report`.
+pub(crate) fn install_hook() {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let report = PanicReport::new(info);
+
+        error!(
+            "Panic on thread {} at {}: {}\n{}",
+            report.thread,
+            report.location.as_deref().unwrap_or("an unknown location"),
+            report.message,
+            report.backtrace
+        );
+        log::logger().flush();
+
+        if let Ok(mut stored) = REPORT.lock() {
+            stored.get_or_insert(report);
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// The first panic since the last call, if there has been one.
+pub fn take_panic_report() -> Option<PanicReport> {
+    REPORT.lock().ok().and_then(|mut report| report.take())
+}
+
+impl PanicReport {
+    fn new(info: &PanicHookInfo) -> Self {
+        let payload = info.payload();
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+
+        Self {
+            message,
+            location: info.location().map(ToString::to_string),
+            thread: thread::current().name().unwrap_or("unnamed").to_string(),
+            backtrace: Backtrace::force_capture().to_string(),
+        }
+    }
+}
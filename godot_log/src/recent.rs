@@ -0,0 +1,84 @@
+use log::{Level, LevelFilter, Metadata, Record};
+use std::collections::VecDeque;
+use std::sync::{Mutex, RwLock};
+use std::thread;
+
+/// How many records are kept for `recent_records`; older ones are dropped.
+pub const RECENT_CAPACITY: usize = 1000;
+
+/// A logged record, kept for display in the game.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Increases by one for every record, so readers can ask for what they haven't seen.
+    pub sequence: u64,
+    pub level: Level,
+    pub module_path: String,
+    pub thread: String,
+    pub message: String,
+}
+
+struct Recent {
+    entries: VecDeque<LogEntry>,
+    next_sequence: u64,
+}
+
+static RECENT: Mutex<Recent> = Mutex::new(Recent {
+    entries: VecDeque::new(),
+    next_sequence: 0,
+});
+
+/// The least severe level kept, whatever the console and file filters drop.
+static LEVEL: RwLock<LevelFilter> = RwLock::new(LevelFilter::Info);
+
+pub(crate) fn set_level(level: LevelFilter) {
+    if let Ok(mut current) = LEVEL.write() {
+        *current = level;
+    }
+}
+
+pub(crate) fn max_level() -> LevelFilter {
+    LEVEL.read().map_or(LevelFilter::Info, |level| *level)
+}
+
+pub(crate) fn enabled(metadata: &Metadata) -> bool {
+    metadata.level() <= max_level()
+}
+
+/// Keeps a record, whose arguments were formatted as `message` before calling this,
+/// since formatting them can panic and the panic hook logs too.
+pub(crate) fn push(record: &Record, message: &str) {
+    let current = thread::current();
+
+    let mut entry = LogEntry {
+        sequence: 0,
+        level: record.level(),
+        module_path: record.module_path().unwrap_or("??").to_string(),
+        thread: current.name().unwrap_or("unnamed").to_string(),
+        message: message.to_string(),
+    };
+
+    if let Ok(mut recent) = RECENT.lock() {
+        entry.sequence = recent.next_sequence;
+        recent.next_sequence += 1;
+
+        if recent.entries.len() == RECENT_CAPACITY {
+            recent.entries.pop_front();
+        }
+        recent.entries.push_back(entry);
+    }
+}
+
+/// The kept records with a sequence number of at least `since`, oldest first.
+pub fn recent_records(since: u64) -> Vec<LogEntry> {
+    RECENT.lock().map_or_else(
+        |_| Vec::new(),
+        |recent| {
+            recent
+                .entries
+                .iter()
+                .filter(|entry| entry.sequence >= since)
+                .cloned()
+                .collect()
+        },
+    )
+}
@@ -3,44 +3,129 @@ use super::messages::ToGui;
 use crate::agent::bi_channel::create_channel;
 use crate::agent::gui_agent::GuiClient;
 use crate::agent::messages::FromGui;
-use crate::board::Board;
-use crate::board_slot::{BoardSlot, SlotPos, CLICK_RELEASED_SIGNAL};
-use crate::card_board_instance::CardBoardInstance;
-use crate::card_instance::CardInstance;
-use crate::end_turn_button::{EndTurnButton, END_TURN_CLICKED_SIGNAL};
-use crate::gui_mana_counter::ManaCounter;
-use crate::hand::{Hand, PLAYER_HAND_CARD_DRAGGED};
+use crate::board::{mapping, Board, BoardShape};
+use crate::board_slot::{
+    BoardSlot, SlotHighlight, SlotPos, BOARD_SLOT_SCENE, CLICK_RELEASED_SIGNAL,
+    CREATURE_INSPECT_REQUESTED_SIGNAL,
+};
+use crate::card_board_instance::{CardBoardInstance, CARD_BOARD_INSTANCE_SCENE};
+use crate::card_inspect::{CardInspect, InspectedCard};
+use crate::card_instance::{CardInstance, CARD_INSTANCE_SCENE};
+use crate::card_text;
+use crate::end_turn_button::{self, EndTurnButton, END_TURN_CLICKED_SIGNAL};
+use crate::error::{GuiError, OrReport};
+use crate::fatal_error::FatalErrorScreen;
+use crate::gui_mana_counter::{self, ManaCounter};
+use crate::hand::{
+    Hand, PLAYER_HAND_CARD_CLICKED, PLAYER_HAND_CARD_DRAGGED, PLAYER_HAND_CARD_DRAG_CANCELLED,
+    PLAYER_HAND_CARD_INSPECT_REQUESTED,
+};
+use crate::input::{
+    self,
+    focus::{self, Direction, Focus},
+};
+use crate::locale;
+use crate::scene_cache;
+use crate::scene_contract::{self, RequiredNode, SceneContract};
+use crate::toast::Toasts;
 use crate::util;
 use crate::util::NodeRef;
-use gdnative::api::utils::NodeExt;
-use gdnative::api::{Area, Camera};
+use gdnative::api::{
+    Area, Camera, InputEventMouse, InputEventScreenDrag, InputEventScreenTouch,
+};
 use gdnative::prelude::*;
-use godot_log::GodotLog;
+use godot_log::{GodotLog, PanicReport};
 use log::{info, warn};
 use salt_engine::cards::UnitCardDefinitionView;
 use salt_engine::game_logic::events::{
     AddCardToHandClientEvent, ClientEventView, CreatureSetClientEvent,
 };
 use salt_engine::game_runner::GameClient;
+use salt_engine::game_state::board::RowId;
 use salt_engine::game_state::GameStatePlayerView;
 use salt_engine::game_state::PlayerId;
+use salt_engine::game_state::UnitCardInstanceId;
+use salt_engine::game_state::UnitCardInstancePlayerView;
 use smol::channel::TryRecvError;
+use std::convert::TryFrom;
 use std::thread::JoinHandle;
 
-const BOARD_SLOT_COUNT: usize = 24;
-const BOARD_SLOT_PATH_PREFIX: &str = "BoardSlot";
 const BOARD_PATH_RELATIVE: &str = "Board";
 const PLAYER_HAND_PATH_RELATIVE: &str = "PlayerHand";
 const END_TURN_BUTTON: &str = "EndTurnButton";
 const MANA_DISPLAY: &str = "ManaCounter";
+const CARD_INSPECT: &str = "CardInspect";
+const CAMERA_PATH: &str = "Camera";
+const NETWORK_THREAD_NAME: &str = "network";
+
+/// The scenes instanced from code, loaded in the background while the main scene starts up.
+const PRELOADED_SCENES: &[&str] = &[
+    BOARD_SLOT_SCENE,
+    CARD_INSTANCE_SCENE,
+    CARD_BOARD_INSTANCE_SCENE,
+];
+
+/// The nodes `World` expects among its children in the main scene.
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "World",
+    scene: None,
+    nodes: &[
+        RequiredNode::new(BOARD_PATH_RELATIVE, "Spatial").with_script("Board"),
+        RequiredNode::new(PLAYER_HAND_PATH_RELATIVE, "Spatial").with_script("Hand"),
+        RequiredNode::new(END_TURN_BUTTON, "Spatial")
+            .with_script("EndTurnButton")
+            .containing(&end_turn_button::SCENE_CONTRACT),
+        RequiredNode::new(MANA_DISPLAY, "Control")
+            .with_script("ManaCounter")
+            .containing(&gui_mana_counter::SCENE_CONTRACT),
+        RequiredNode::new(CARD_INSPECT, "Control").with_script("CardInspect"),
+        RequiredNode::new(CAMERA_PATH, "Camera"),
+    ],
+};
+
+/// What the card inspect overlay is describing.
+#[derive(Debug, Clone, PartialEq)]
+enum InspectTarget {
+    /// The hand card at this path.
+    HandCard(String),
+
+    /// The creature in the board slot at this path.
+    Creature(String),
+}
 
 /// State for maintaining certain UI-specific values over the course of the game.
 #[derive(Debug, Default)]
 struct WorldState {
     player_id: Option<PlayerId>,
     opponent_id: Option<PlayerId>,
+    is_player_turn: bool,
     dragging_hand_card: Option<NodePath>,
+    selected_hand_card: Option<NodePath>,
+    focus: Focus,
+    slots_highlighted: bool,
     card_to_summon: Option<(NodeRef<BoardSlot, Spatial>, NodePath)>,
+    drop_preview: Option<(SlotPos, Ref<Spatial>)>,
+    touch_position: Option<Vector2>,
+    inspecting: Option<InspectTarget>,
+
+    /// The localization key of the end turn button's text, once a turn has started.
+    end_turn_text_key: Option<&'static str>,
+
+    /// The `locale::revision` the text was last drawn in.
+    language_revision: u64,
+
+    /// Whether the inspect overlay needs redrawing, e.g. after the inspected creature changed.
+    is_inspect_stale: bool,
+
+    /// Whether a panic has been reported; the game stops responding once it has.
+    has_fatal_error: bool,
+
+    /// Whether the world's own nodes broke their scene contract, in which case
+    /// nothing is wired up to them and the game doesn't respond.
+    is_scene_broken: bool,
+
+    /// Whether the scenes instanced from code have been checked, which waits on preloading.
+    are_instanced_scenes_checked: bool,
     player_mana_limit: usize,
     enemy_mana_limit: usize,
     player_mana_count: usize,
@@ -57,6 +142,7 @@ pub struct World {
     message_channel: BiChannel<FromGui, ToGui>,
     end_turn_button: NodeRef<EndTurnButton, Spatial>,
     mana_display: NodeRef<ManaCounter, Control>,
+    card_inspect: NodeRef<CardInspect, Control>,
     player_hand: NodeRef<Hand, Spatial>,
 }
 
@@ -64,18 +150,22 @@ impl World {
     fn new(_owner: &Node) -> Self {
         let (gui_side_channel, network_side_channel) = create_channel::<FromGui, ToGui>();
 
-        let handle = std::thread::spawn(move || {
-            smol::block_on(async {
-                // The agent is a connection between the gui client and gui frontend.
-                let make_agent = |player_id| {
-                    Box::new(GuiClient::new_with_id(network_side_channel, player_id))
-                        as Box<dyn GameClient>
-                };
-
-                // The client is a connection between the remote game server and the gui client.
-                websocket_client::start(make_agent).await.unwrap();
-            });
-        });
+        // Named so its log lines, which are forwarded from the main thread, can be told apart.
+        let handle = std::thread::Builder::new()
+            .name(NETWORK_THREAD_NAME.to_string())
+            .spawn(move || {
+                smol::block_on(async {
+                    // The agent is a connection between the gui client and gui frontend.
+                    let make_agent = |player_id| {
+                        Box::new(GuiClient::new_with_id(network_side_channel, player_id))
+                            as Box<dyn GameClient>
+                    };
+
+                    // The client is a connection between the remote game server and the gui client.
+                    websocket_client::start(make_agent).await.unwrap();
+                });
+            })
+            .expect("Failed to spawn the network thread");
 
         info!("Websocket server started on a new thread.");
 
@@ -90,6 +180,7 @@ impl World {
             message_channel: gui_side_channel,
             end_turn_button: NodeRef::<EndTurnButton, Spatial>::from_path(END_TURN_BUTTON),
             mana_display: NodeRef::<ManaCounter, Control>::from_path(MANA_DISPLAY),
+            card_inspect: NodeRef::<CardInspect, Control>::from_path(CARD_INSPECT),
             player_hand: NodeRef::<Hand, Spatial>::from_path(PLAYER_HAND_PATH_RELATIVE),
         }
     }
@@ -98,6 +189,9 @@ impl World {
     fn observe_notifier_event(&mut self, event: ClientEventView, owner: TRef<Node>) {
         info!("Gui observes event: {:?}", event);
 
+        // Any event might change the inspected card's stats.
+        self.state.is_inspect_stale = true;
+
         match event {
             ClientEventView::AddCardToHand(e) => self.observe_add_card_to_hand(e, owner),
             ClientEventView::UnitSet(e) => self.observe_creature_set_event(e, owner),
@@ -129,21 +223,15 @@ impl World {
             let unused = self.state.player_mana_count;
             let limit = self.state.player_mana_limit;
             self.mana_display
-                .resolve_instance()
-                .map(|c, _| {
-                    c.set_display(unused, limit);
-                })
-                .expect("Unable to set mana label");
+                .try_map_mut(|c, _| c.set_display(unused, limit))
+                .or_report();
         } else {
             self.state.enemy_mana_count -= spent_mana_count;
             let limit = self.state.enemy_mana_limit;
             let unused = self.state.enemy_mana_count;
             self.mana_display
-                .resolve_instance()
-                .map(|c, _| {
-                    c.set_display(unused, limit);
-                })
-                .expect("Unable to set mana label");
+                .try_map_mut(|c, _| c.set_display(unused, limit))
+                .or_report();
         }
     }
 
@@ -158,106 +246,168 @@ impl World {
             let unused = self.state.player_mana_count;
             let limit = self.state.player_mana_limit;
             self.mana_display
-                .resolve_instance()
-                .map(|c, _| {
-                    c.set_display(unused, limit);
-                })
-                .expect("Unable to set mana label");
+                .try_map_mut(|c, _| c.set_display(unused, limit))
+                .or_report();
         } else {
             self.state.enemy_mana_limit += mana_gain_count;
             let limit = self.state.enemy_mana_limit;
             let unused = self.state.enemy_mana_count;
             self.mana_display
-                .resolve_instance()
-                .map(|c, _| {
-                    c.set_display(unused, limit);
-                })
-                .expect("Unable to set mana label");
+                .try_map_mut(|c, _| c.set_display(unused, limit))
+                .or_report();
         }
     }
 
     fn observe_turn_started(&mut self, player: PlayerId, _owner: TRef<Node>) {
-        let button_text;
-        if player == self.state.player_id.unwrap() {
+        let button_text_key;
+        self.state.is_player_turn = player == self.state.player_id.unwrap();
+        if self.state.is_player_turn {
             // TODO: this is a big hack - we add +1 because we know we gain +1 mana upon turn start.
             // But in reality, we should be responding to the "gain mana" event, not the "turn start" event.
             self.state.player_mana_count = self.state.player_mana_limit + 1;
-            button_text = "End turn";
+            button_text_key = "turn.end";
         } else {
             self.state.enemy_mana_count = self.state.enemy_mana_limit + 1;
-            button_text = "(Enemy turn)";
+            button_text_key = "turn.enemy";
         };
 
-        let button = self.end_turn_button.resolve_instance();
-        button
-            .map(|t, _| t.set_text(button_text))
-            .expect("Could not set_text on textbox");
+        self.state.end_turn_text_key = Some(button_text_key);
+        self.update_end_turn_text();
+    }
+
+    fn update_end_turn_text(&self) {
+        let button_text = if let Some(key) = self.state.end_turn_text_key {
+            locale::tr(key)
+        } else {
+            return;
+        };
+
+        self.end_turn_button
+            .try_map(|t, _| t.set_text(&button_text))
+            .or_report();
+    }
+
+    /// Redraws the text the World owns whenever the language changes.
+    fn update_language(&mut self) {
+        let revision = locale::revision();
+        if revision == self.state.language_revision {
+            return;
+        }
+
+        self.state.language_revision = revision;
+
+        self.update_end_turn_text();
+        self.mana_display
+            .try_map(|c, _| c.refresh_text())
+            .or_report();
+        self.state.is_inspect_stale = true;
     }
 
     fn observe_add_card_to_hand(&self, event: AddCardToHandClientEvent, _owner: TRef<Node>) {
         info!("World is adding a card to the player's hand.");
-        let hand = self.player_hand.resolve_instance();
-
         if event.player_id == self.state.player_id.unwrap() {
-            hand.map_mut(|h, n| {
-                h.add_card(&event.card.unwrap(), n);
-            })
-            .expect("failed to add card to hand");
+            self.player_hand
+                .try_map_mut(|h, n| h.add_card(&event.card.unwrap(), n))
+                .or_report();
         } else {
             // Not handling the scenario where opponent draws a card yet
             info!("Saw that an opponent added card to hand.");
         }
     }
 
-    fn observe_creature_set_event(&self, event: CreatureSetClientEvent, owner: TRef<Node>) {
+    fn observe_creature_set_event(&self, event: CreatureSetClientEvent, _owner: TRef<Node>) {
         info!("World saw a summon event.");
-        let slot_pos = SlotPos {
-            row_id: event.pos.row_id,
-            is_friendly: event.pos.player_id == self.state.player_id.unwrap(),
-            index: event.pos.row_index,
-        };
+        let slot_pos = mapping::from_board_pos(&event.pos, self.state.player_id.unwrap());
 
-        // let board = self.board();
-        // let slot = board.map(|b, _| b.get_slot(slot_pos)).unwrap();
-
-        let slot_index = self.boardslot_from_pos(owner, slot_pos);
-        let slot_path = format!(
-            "{}/{}{}",
-            BOARD_PATH_RELATIVE, BOARD_SLOT_PATH_PREFIX, slot_index
-        );
-
-        info!("Summoning creature to slot path: {}", slot_path);
-
-        let slot: NodeRef<BoardSlot, Spatial> = NodeRef::from_parent_ref(slot_path, owner);
+        let slot = if let Some(slot) = self.slot_ref_at(slot_pos).or_report() {
+            slot
+        } else {
+            return;
+        };
 
-        info!("Generated slot noderef.");
+        info!("Summoning creature to slot path: {}", slot.path());
 
-        let card_board_instance = CardBoardInstance::new_instance();
+        let card_board_instance =
+            if let Some(card_board_instance) = CardBoardInstance::new_instance().or_report() {
+                card_board_instance
+            } else {
+                return;
+            };
 
         info!("Generated card board instance.");
 
-        card_board_instance
+        let creature_id = event.card.id();
+
+        let initialized = card_board_instance
             .map_mut(|card, _| {
                 let definition = event.card.definition();
                 card.set_title(definition.title());
-                card.set_stats(format!("{}/{}", definition.attack(), definition.health()));
+                card.set_stats(card_text::format_stats(
+                    event.card.attack(),
+                    event.card.health(),
+                    definition.attack(),
+                    definition.health(),
+                ));
+                card.set_view(event.card.clone());
             })
-            .expect("Could not update values on card board instance");
+            .map_err(|e| GuiError::borrowed::<CardBoardInstance>(CARD_BOARD_INSTANCE_SCENE, e));
+
+        if initialized.or_report().is_none() {
+            card_board_instance.into_base().free();
+            return;
+        }
 
-        info!("Resolving path to slot...");
-        let slot = slot.resolve_instance();
-        info!("Resolved path to slot.");
+        self.clear_creature_elsewhere(creature_id, slot_pos);
 
-        slot.map_mut(|a, b| a.receive_summon_z(card_board_instance, b))
-            .expect("Failed to receive summon for slot");
+        slot.try_map_mut(|a, b| a.replace_creature(creature_id, card_board_instance, b))
+            .or_report();
+    }
+
+    /// Clears a creature from any slot other than `pos`, as a creature is only ever in one slot.
+    fn clear_creature_elsewhere(&self, creature_id: UnitCardInstanceId, pos: SlotPos) {
+        for slot in self.board_slots() {
+            let is_elsewhere = slot
+                .try_map(|s, _| s.pos() != pos && s.creature_id() == Some(creature_id))
+                .or_report()
+                .unwrap_or(false);
+
+            if is_elsewhere {
+                slot.try_map_mut(|s, n| s.clear_creature(n)).or_report();
+            }
+        }
     }
 
     /// Invoked each frame where there is a message from the server with a state update.
-    fn update_from_state(&mut self, state: GameStatePlayerView, _owner: TRef<Node>) {
+    fn update_from_state(&mut self, state: GameStatePlayerView, owner: TRef<Node>) {
         if self.state.opponent_id.is_none() {
             self.state.opponent_id = Some(state.opponent_id());
             info!("My opponent is: {:?}", state.opponent_id());
         }
+
+        if let Some(player_id) = self.state.player_id {
+            if self.board_shape().or_report() == Some(None) {
+                self.build_board(BoardShape::from_state(&state, player_id), owner);
+            }
+        }
+    }
+
+    /// Builds the board's slots from the engine's board shape, then listens to them.
+    fn build_board(&self, shape: BoardShape, owner: TRef<Node>) {
+        let is_built = self
+            .state
+            .board
+            .try_map_mut(|b, n| b.build(shape, n))
+            .or_report()
+            .is_some();
+
+        if is_built {
+            self.connect_boardslot_signals(owner);
+        }
+    }
+
+    /// The board's shape, or `None` if the board hasn't been built yet.
+    fn board_shape(&self) -> Result<Option<BoardShape>, GuiError> {
+        self.state.board.try_map(|b, _| b.shape().cloned())
     }
 
     /// Get a card instance given its path.
@@ -265,82 +415,504 @@ impl World {
         &self,
         path: impl AsRef<str>,
         owner: TRef<'a, Node>,
-    ) -> Option<RefInstance<'a, CardInstance, Shared>> {
+    ) -> Result<RefInstance<'a, CardInstance, Shared>, GuiError> {
         util::get_as(path, owner)
     }
 
-    fn board(&self) -> RefInstance<Board, Shared> {
-        self.state.board.resolve_instance()
-        //unsafe { owner.as_ref().get_node_as::<Spatial>(BOARD_PATH_RELATIVE) }
+    fn camera<'a>(&self, owner: TRef<'a, Node>) -> Result<TRef<'a, Camera>, GuiError> {
+        util::get_node(CAMERA_PATH, owner)
+    }
+
+    /// Every `BoardSlot` on the board.
+    fn board_slots(&self) -> Vec<NodeRef<BoardSlot, Spatial>> {
+        self.state
+            .board
+            .try_map(|b, _| b.slots())
+            .or_report()
+            .unwrap_or_default()
     }
 
-    fn camera(&self, owner: TRef<Node>) -> Option<TRef<Camera>> {
-        unsafe { owner.as_ref().get_node_as::<Camera>("Camera") }
+    /// Whether the player may summon the given card to the given slot right now.
+    fn is_valid_summon_target(&self, card: &UnitCardInstancePlayerView, slot: &BoardSlot) -> bool {
+        let pos = slot.pos();
+        let cost = card.definition().cost();
+        let can_afford = usize::try_from(cost).map_or(false, |c| c <= self.state.player_mana_count);
+
+        self.state.is_player_turn
+            && pos.is_friendly
+            && pos.row_id != RowId::Hero
+            && !slot.is_occupied()
+            && can_afford
     }
 
-    /// Given a `SlotPos`, returns its corresponding board slot number.
-    fn boardslot_from_pos(&self, _owner: TRef<Node>, pos: SlotPos) -> usize {
-        let row_len = BOARD_SLOT_COUNT / 4;
+    /// The view of the hand card being dragged or, failing that, the selected one.
+    fn active_card_view(&self, owner: TRef<Node>) -> Option<UnitCardInstancePlayerView> {
+        let card_path = self
+            .state
+            .dragging_hand_card
+            .as_ref()
+            .or_else(|| self.state.selected_hand_card.as_ref())?;
 
-        let offset = if pos.is_friendly {
-            let player_offset = row_len * 2;
+        let card = self.card_instance(card_path.to_string(), owner).ok()?;
+        card.map(|c, _| c.expect_view().clone()).ok()
+    }
 
-            let row_offset = match pos.row_id {
-                salt_engine::game_state::board::RowId::FrontRow => 0,
-                salt_engine::game_state::board::RowId::BackRow => row_len,
-                salt_engine::game_state::board::RowId::Hero => todo!("hero not done yet"),
-            };
+    /// Refreshes the slot highlights, and the drop preview for the hand card being dragged.
+    /// The hovered slot is the one under the keyboard/gamepad cursor if there is one,
+    /// otherwise the one under the touch or mouse.
+    fn update_slot_feedback(&mut self, owner: TRef<Node>) {
+        let card_view = self.active_card_view(owner);
+
+        let hovered_slot = match self.state.focus {
+            Focus::Board(pos) => self.slot_ref_at(pos).ok(),
+            _ if card_view.is_some() => {
+                let pointer_pos = self.pointer_position(owner);
+                self.find_overlapping_boardslot(owner, pointer_pos)
+            }
+            _ => None,
+        };
+
+        if card_view.is_none() && hovered_slot.is_none() {
+            if self.state.slots_highlighted {
+                self.clear_slot_highlights();
+            }
+            return;
+        }
+
+        let hovered_pos = hovered_slot
+            .as_ref()
+            .and_then(|slot| slot.try_map(|s, _| s.pos()).or_report());
+
+        self.update_slot_highlights(card_view.as_ref(), hovered_pos);
+
+        if let (Some(card_view), true) = (&card_view, self.state.dragging_hand_card.is_some()) {
+            self.update_drop_preview(card_view, hovered_slot);
+        }
+    }
+
+    /// Highlights the slots the given card can be summoned to and dims the rest.
+    /// The hovered slot gets a stronger highlight, even with no card to place.
+    fn update_slot_highlights(
+        &mut self,
+        card_view: Option<&UnitCardInstancePlayerView>,
+        hovered_pos: Option<SlotPos>,
+    ) {
+        for slot in self.board_slots() {
+            slot.try_map_mut(|s, _| {
+                let is_hovered = hovered_pos == Some(s.pos());
+
+                let highlight = match card_view {
+                    Some(card_view) if !self.is_valid_summon_target(card_view, s) => {
+                        SlotHighlight::Invalid
+                    }
+                    _ if is_hovered => SlotHighlight::Hover,
+                    Some(_) => SlotHighlight::Valid,
+                    None => SlotHighlight::Normal,
+                };
+
+                s.set_highlight(highlight);
+            })
+            .or_report();
+        }
+
+        self.state.slots_highlighted = true;
+    }
+
+    /// Returns every board slot to its normal appearance.
+    fn clear_slot_highlights(&mut self) {
+        for slot in self.board_slots() {
+            slot.try_map_mut(|s, _| s.set_highlight(SlotHighlight::Normal))
+                .or_report();
+        }
 
-            let index_offset = pos.index;
+        self.state.slots_highlighted = false;
+    }
+
+    /// A reference to the board slot at the given position.
+    fn slot_ref_at(&self, pos: SlotPos) -> Result<NodeRef<BoardSlot, Spatial>, GuiError> {
+        Ok(self.state.board.try_map(|b, _| b.slot_at(pos))??)
+    }
+
+    /// A reference to the board slot with the given node path.
+    fn slot_ref_at_path(&self, path: &str) -> Result<NodeRef<BoardSlot, Spatial>, GuiError> {
+        Ok(self.state.board.try_map(|b, _| b.slot_at_path(path))??)
+    }
+
+    /// Moves the keyboard/gamepad cursor through the hand, onto the board and back.
+    fn move_focus(&mut self, direction: Direction) {
+        let shape = self.board_shape().or_report().flatten();
+        let hand_index = self.selected_hand_index().unwrap_or(0);
+
+        self.state.focus = focus::move_focus(
+            self.state.focus,
+            direction,
+            self.hand_len(),
+            hand_index,
+            shape.as_ref(),
+        );
+
+        info!("Focus moved {:?} to {:?}", direction, self.state.focus);
+    }
+
+    /// Toggles the inspect overlay for whatever the keyboard/gamepad cursor is on.
+    fn inspect_focus(&mut self) {
+        let target = match self.state.focus {
+            Focus::Unfocused => None,
+            Focus::Hand(index) => self
+                .player_hand
+                .try_map(|h, _| h.card_path(index))
+                .or_report()
+                .flatten()
+                .map(InspectTarget::HandCard),
+            Focus::Board(pos) => self
+                .slot_ref_at(pos)
+                .ok()
+                .map(|slot| InspectTarget::Creature(slot.path().to_string())),
+        };
+
+        if let Some(target) = target {
+            let is_inspecting = self.state.inspecting.as_ref() != Some(&target);
+            self.set_inspect_target(target, is_inspecting);
+        }
+    }
 
-            player_offset + row_offset + index_offset
+    /// Starts inspecting the target, or stops if it is the one being inspected.
+    /// Requests to stop inspecting anything else are stale and ignored.
+    fn set_inspect_target(&mut self, target: InspectTarget, is_inspecting: bool) {
+        if is_inspecting {
+            self.state.inspecting = Some(target);
+        } else if self.state.inspecting.as_ref() == Some(&target) {
+            self.state.inspecting = None;
         } else {
-            let player_offset = 0;
+            return;
+        }
 
-            let row_offset = match pos.row_id {
-                salt_engine::game_state::board::RowId::FrontRow => row_len,
-                salt_engine::game_state::board::RowId::BackRow => 0,
-                salt_engine::game_state::board::RowId::Hero => todo!("hero not done yet"),
-            };
+        self.state.is_inspect_stale = true;
+    }
+
+    /// Shows the inspected card in the overlay, or hides it if nothing is inspected.
+    fn update_card_inspect(&mut self, owner: TRef<Node>) {
+        if !self.state.is_inspect_stale {
+            return;
+        }
+
+        self.state.is_inspect_stale = false;
+
+        let card = self
+            .state
+            .inspecting
+            .as_ref()
+            .and_then(|target| self.inspected_card(target, owner));
+
+        self.card_inspect
+            .try_map(|c, _| match &card {
+                Some(card) => c.show_card(card),
+                None => c.hide_card(),
+            })
+            .or_report();
+    }
+
+    fn inspected_card(&self, target: &InspectTarget, owner: TRef<Node>) -> Option<InspectedCard> {
+        match target {
+            InspectTarget::HandCard(path) => {
+                let card = self.card_instance(path, owner).ok()?;
+                card.map(|c, _| InspectedCard::from_view(c.expect_view(), true)).ok()
+            }
+            InspectTarget::Creature(path) => {
+                let slot = self.slot_ref_at_path(path).ok()?;
+                let (creature, pos) = slot
+                    .try_map(|s, _| (s.creature_node(), s.pos()))
+                    .or_report()?;
+
+                let creature = unsafe { creature?.assume_safe() };
+                let creature = creature.cast_instance::<CardBoardInstance>()?;
+
+                creature
+                    .map(|c, _| {
+                        c.view()
+                            .map(|view| InspectedCard::from_view(view, pos.is_friendly))
+                    })
+                    .ok()?
+            }
+        }
+    }
+
+    /// Confirms whatever the keyboard/gamepad cursor is on.
+    /// On a hand card, moves the cursor onto the board to pick a slot for it;
+    /// on a slot, places the selected card there.
+    fn confirm_focus(&mut self, owner: TRef<Node>) {
+        let focus = self.state.focus;
+        match focus {
+            Focus::Unfocused => {}
+            Focus::Hand(_) => {
+                let entry_pos = self.first_valid_slot(owner);
+                self.state.focus = Focus::Board(entry_pos.unwrap_or_else(focus::board_entry_pos));
+            }
+            Focus::Board(pos) => {
+                let index = self.selected_hand_index();
+
+                if let (Some(card_path), Some(slot)) =
+                    (self.state.selected_hand_card.take(), self.slot_ref_at(pos).ok())
+                {
+                    self.state.card_to_summon = Some((slot, card_path));
+                    self.state.focus = Focus::Hand(index.unwrap_or(0));
+                }
+            }
+        }
+    }
+
+    /// Backs the keyboard/gamepad cursor out one step: from the board to the hand,
+    /// and from the hand to nothing.
+    fn cancel_focus(&mut self) {
+        let focus = self.state.focus;
+        self.state.focus = match focus {
+            Focus::Board(_) if self.state.selected_hand_card.is_some() => {
+                Focus::Hand(self.selected_hand_index().unwrap_or(0))
+            }
+            _ => {
+                self.state.selected_hand_card = None;
+                Focus::Unfocused
+            }
+        };
+    }
+
+    /// While the cursor is in the hand, selects the card under it.
+    fn update_focused_hand_card(&mut self) {
+        let index = if let Focus::Hand(index) = self.state.focus {
+            index
+        } else {
+            return;
+        };
+
+        let hand_len = self.hand_len();
+
+        if hand_len == 0 {
+            self.state.focus = Focus::Unfocused;
+            return;
+        }
+
+        let index = index.min(hand_len - 1);
+        self.state.focus = Focus::Hand(index);
+
+        let card_path = self
+            .player_hand
+            .try_map(|h, _| h.card_path(index))
+            .or_report()
+            .flatten();
+
+        self.state.selected_hand_card = card_path.map(|path| NodePath::from_str(&path));
+    }
+
+    /// The first slot the selected card could be summoned to, if any.
+    fn first_valid_slot(&self, owner: TRef<Node>) -> Option<SlotPos> {
+        let card_view = self.active_card_view(owner)?;
+
+        self.board_slots().into_iter().find_map(|slot| {
+            slot.try_map(|s, _| {
+                if self.is_valid_summon_target(&card_view, s) {
+                    Some(s.pos())
+                } else {
+                    None
+                }
+            })
+            .or_report()
+            .flatten()
+        })
+    }
+
+    fn selected_hand_index(&self) -> Option<usize> {
+        let selected = self.state.selected_hand_card.as_ref()?.to_string();
+
+        self.player_hand
+            .try_map(|h, _| h.index_of(&selected))
+            .or_report()
+            .flatten()
+    }
+
+    fn hand_len(&self) -> usize {
+        self.player_hand
+            .try_map(|h, _| h.hand_len())
+            .or_report()
+            .unwrap_or_default()
+    }
+
+    /// Shows a translucent copy of the dragged card in the hovered slot,
+    /// following the cursor from slot to slot.
+    fn update_drop_preview(
+        &mut self,
+        card_view: &UnitCardInstancePlayerView,
+        hovered_slot: Option<NodeRef<BoardSlot, Spatial>>,
+    ) {
+        let slot = if let Some(slot) = hovered_slot {
+            slot
+        } else {
+            self.clear_drop_preview();
+            return;
+        };
+
+        let slot = if let Some(slot) = slot.try_resolve_instance().or_report() {
+            slot
+        } else {
+            return;
+        };
+
+        let slot_pos = if let Ok(slot_pos) = slot.map(|s, _| s.pos()) {
+            slot_pos
+        } else {
+            return;
+        };
+
+        if let Some((preview_pos, _)) = &self.state.drop_preview {
+            if *preview_pos == slot_pos {
+                return;
+            }
+        }
 
-            let index_offset = pos.index;
+        self.clear_drop_preview();
 
-            player_offset + row_offset + index_offset
+        let preview = if let Some(preview) = CardBoardInstance::new_instance().or_report() {
+            preview
+        } else {
+            return;
         };
 
-        // In the Godot world, slots begin at index 1 instead of 0
-        offset + 1
+        let initialized = preview
+            .map_mut(|card, n| {
+                let definition = card_view.definition();
+                card.set_title(definition.title());
+                card.set_stats(card_text::format_stats(
+                    card_view.attack(),
+                    card_view.health(),
+                    definition.attack(),
+                    definition.health(),
+                ));
+                card.set_ghost(true, n);
+            })
+            .map_err(|e| GuiError::borrowed::<CardBoardInstance>(CARD_BOARD_INSTANCE_SCENE, e));
+
+        if initialized.or_report().is_none() {
+            preview.into_base().free();
+            return;
+        }
+
+        let preview = preview.into_base().into_shared();
+        slot.base().add_child(preview, false);
+
+        self.state.drop_preview = Some((slot_pos, preview));
+    }
+
+    fn clear_drop_preview(&mut self) {
+        if let Some((_, preview)) = self.state.drop_preview.take() {
+            let preview = unsafe { preview.assume_safe() };
+
+            if let Some(parent) = preview.get_parent() {
+                unsafe { parent.assume_safe() }.remove_child(preview);
+            }
+
+            preview.queue_free();
+        }
+    }
+
+    /// Keeps the raised, outlined card in the hand in sync with the selection.
+    fn update_selected_hand_card(&self) {
+        let selected = self
+            .state
+            .selected_hand_card
+            .as_ref()
+            .map(NodePath::to_string);
+
+        self.player_hand
+            .try_map(|h, _| h.set_selected_card(selected.as_deref()))
+            .or_report();
+    }
+
+    /// Where the player is pointing: the current touch if there is one, otherwise the mouse.
+    fn pointer_position(&self, owner: TRef<Node>) -> Vector2 {
+        if let Some(touch_position) = self.state.touch_position {
+            return touch_position;
+        }
+
+        let viewport = owner.get_viewport().unwrap();
+        let viewport = unsafe { viewport.assume_safe() };
+        viewport.get_mouse_position()
     }
 }
 
 #[methods]
 impl World {
+    /// Invoked by Godot before any child is ready, so the scenes they instance
+    /// can start loading first.
+    #[export]
+    fn _enter_tree(&mut self, owner: TRef<Node>) {
+        GodotLog::init();
+        locale::init();
+        input::init();
+
+        scene_cache::preload(PRELOADED_SCENES);
+
+        // Checked before the children are readied, as they expect their own nodes to be there.
+        self.state.is_scene_broken = !scene_contract::validate_world(owner);
+    }
+
+    #[export]
+    fn _exit_tree(&mut self, _owner: TRef<Node>) {
+        scene_cache::log_metrics();
+    }
+
     /// Invoked by Godot when this instance is done initializing.
     #[export]
     fn _ready(&mut self, owner: TRef<Node>) {
-        GodotLog::init();
         info!("World initialized.  Hello.");
 
-        self.state.board.init_from_parent_ref(owner);
+        Toasts::add_to(owner);
 
-        self.end_turn_button.init_from_parent_ref(owner);
-        self.mana_display.init_from_parent_ref(owner);
-        self.player_hand.init_from_parent_ref(owner);
+        if self.state.is_scene_broken {
+            warn!("Not wiring up the world's nodes, as they aren't laid out as expected.");
+            return;
+        }
+
+        self.state.board.try_init_from_parent_ref(owner).or_report();
+
+        self.end_turn_button.try_init_from_parent_ref(owner).or_report();
+        self.mana_display.try_init_from_parent_ref(owner).or_report();
+        self.card_inspect.try_init_from_parent_ref(owner).or_report();
+        self.player_hand.try_init_from_parent_ref(owner).or_report();
 
-        self.connect_boardslot_signals(owner);
         self.connect_hand_card_dragged(owner);
         self.connect_end_turn_clicked(owner);
-        // self.init_board_slot_pos(owner);
     }
 
     /// Invoked every frame by Godot.
     #[export]
     fn _process(&mut self, owner: TRef<Node>, _delta: f64) {
+        GodotLog::flush_pending();
+        scene_cache::poll();
+
+        if !self.state.are_instanced_scenes_checked && !scene_cache::is_preloading() {
+            self.state.are_instanced_scenes_checked = true;
+            scene_contract::validate_instanced_scenes();
+        }
+
+        if let Some(report) = godot_log::take_panic_report() {
+            self.show_fatal_error(&report, owner);
+        }
+
+        if self.state.has_fatal_error || self.state.is_scene_broken {
+            return;
+        }
+
         // If we have a card queued up for summoning, attempt to summon it.
         if let Some((slot_path, card_path)) = self.state.card_to_summon.take() {
+            self.set_inspect_target(InspectTarget::HandCard(card_path.to_string()), false);
             self.summon_card_from_hand(slot_path, card_path, owner);
         }
 
+        self.update_focused_hand_card();
+        self.update_slot_feedback(owner);
+        self.update_selected_hand_card();
+        self.update_language();
+        self.update_card_inspect(owner);
+
         let message = match self.message_channel.try_recv() {
             Ok(msg) => msg,
             Err(TryRecvError::Closed) => return, // todo: display something?
@@ -361,14 +933,37 @@ impl World {
         owner: TRef<Node>,
     ) {
         info!("Summoning card from within _process().");
-        let card_inst = self
+        let card_view = self
             .card_instance(card_path.to_string(), owner)
-            .expect("Could not find card instance.");
+            .or_report()
+            .and_then(|card| card.map(|a, _| a.expect_view().clone()).ok());
+
+        let card_view = if let Some(card_view) = card_view {
+            card_view
+        } else {
+            return;
+        };
 
-        let card_instance_id = card_inst.map(|a, _| a.expect_view().id()).unwrap();
+        let card_instance_id = card_view.id();
 
-        let slot_pos = slot_path.resolve_instance().map(|a, _| a.pos()).unwrap();
-        let board_pos = slot_pos.into_board_slot(self.state.player_id.unwrap());
+        let target = slot_path
+            .try_map(|s, _| (self.is_valid_summon_target(&card_view, s), s.pos()))
+            .or_report();
+
+        let slot_pos = match target {
+            Some((true, slot_pos)) => slot_pos,
+            Some((false, _)) => {
+                info!("Card can't be summoned to that slot; leaving it in the hand.");
+                return;
+            }
+            None => return,
+        };
+
+        let board_pos = mapping::to_board_pos(
+            slot_pos,
+            self.state.player_id.unwrap(),
+            self.state.opponent_id.unwrap(),
+        );
 
         self.message_channel
             .send_blocking(FromGui::SummonFromHandToSlotRequest {
@@ -377,23 +972,160 @@ impl World {
             })
             .expect("Failed to send request from gui to network thread.");
 
-        let hand_card: NodeRef<CardInstance, Spatial> =
-            NodeRef::from_parent_ref(card_path.to_string(), owner);
+        self.player_hand
+            .try_map_mut(|h, n| h.remove_card(card_path.to_string(), n))
+            .or_report();
+    }
+
+    /// Invoked by Godot for every input event, before anything can consume it.
+    /// Keeps track of the touch position, which Godot doesn't expose like the mouse's.
+    #[export]
+    fn _input(&mut self, _owner: TRef<Node>, event: Ref<InputEvent>) {
+        let event = unsafe { event.assume_safe() };
+
+        if let Some(drag) = event.cast::<InputEventScreenDrag>() {
+            self.state.touch_position = Some(drag.position());
+        } else if let Some(touch) = event.cast::<InputEventScreenTouch>() {
+            self.state.touch_position = if touch.is_pressed() {
+                Some(touch.position())
+            } else {
+                None
+            };
+        } else if let Some(mouse) = event.cast::<InputEventMouse>() {
+            if !input::is_emulated_from_touch(&mouse) {
+                self.state.touch_position = None;
+            }
+        }
+    }
+
+    /// Invoked by Godot for input that no control or picked object consumed.
+    /// Escape, or clicking or tapping anything other than a board slot,
+    /// deselects the selected hand card.
+    #[export]
+    fn _unhandled_input(&mut self, owner: TRef<Node>, event: Ref<InputEvent>) {
+        if self.state.has_fatal_error || self.state.is_scene_broken {
+            return;
+        }
+
+        let event = unsafe { event.assume_safe() };
+
+        if self.handle_focus_input(event, owner) {
+            if let Some(tree) = owner.get_tree() {
+                unsafe { tree.assume_safe() }.set_input_as_handled();
+            }
+
+            return;
+        }
+
+        if self.state.selected_hand_card.is_none() {
+            return;
+        }
+
+        if !input::is_select_released(&event) {
+            return;
+        }
+
+        let position = if let Some(touch) = event.cast::<InputEventScreenTouch>() {
+            touch.position()
+        } else if let Some(click) = event.cast::<InputEventMouse>() {
+            click.position()
+        } else {
+            return;
+        };
+
+        // Slots are picked after unhandled input, so slot clicks are left to the slot signal.
+        let is_over_slot = self.find_overlapping_boardslot(owner, position).is_some();
+
+        if !is_over_slot {
+            info!("World deselected hand card on click elsewhere.");
+            self.state.selected_hand_card = None;
+            self.state.focus = Focus::Unfocused;
+        }
+    }
+
+    /// Handles the keyboard/gamepad actions, returning whether the event was consumed.
+    /// A dragged card handles its own cancel, so nothing here applies mid-drag.
+    fn handle_focus_input(&mut self, event: TRef<InputEvent>, owner: TRef<Node>) -> bool {
+        if self.state.dragging_hand_card.is_some() {
+            return false;
+        }
+
+        let direction = [
+            (input::FOCUS_LEFT, Direction::Left),
+            (input::FOCUS_RIGHT, Direction::Right),
+            (input::FOCUS_UP, Direction::Up),
+            (input::FOCUS_DOWN, Direction::Down),
+        ]
+        .iter()
+        .find(|(action, _)| event.is_action_pressed(*action, true))
+        .map(|&(_, direction)| direction);
+
+        if let Some(direction) = direction {
+            self.move_focus(direction);
+        } else if event.is_action_pressed(input::CONFIRM, false) {
+            self.confirm_focus(owner);
+        } else if event.is_action_pressed(input::CANCEL, false) {
+            // With nothing to cancel, a right-click is left for the card or slot under the
+            // cursor, which inspects it.
+            if self.state.focus == Focus::Unfocused && self.state.selected_hand_card.is_none() {
+                return false;
+            }
+
+            info!("World backing out of focus {:?} on cancel.", self.state.focus);
+            self.cancel_focus();
+        } else if event.is_action_pressed(input::INSPECT, false)
+            && self.state.focus != Focus::Unfocused
+        {
+            self.inspect_focus();
+        } else if event.is_action_pressed(input::END_TURN, false) {
+            self.request_end_turn();
+        } else {
+            return false;
+        }
 
-        let hand_card = hand_card.resolve_instance();
-        hand_card.base().queue_free();
+        true
     }
 
     /// Invoked by a signal whenever a boardslot has a "click release" action.
-    /// If there's currently a "dragged card" active, this means the player
-    /// is attempting to summon the dragged card to the given boardslot.
+    /// If there's currently a selected hand card, this means the player
+    /// is attempting to summon the selected card to the given boardslot.
     #[export]
-    fn on_boardslot_click_released(&self, owner: TRef<Node>, data: Variant) {
+    fn on_boardslot_click_released(&mut self, owner: TRef<Node>, data: Variant) {
         info!(
             "world on_boardslot_click_released for {:?} with data: {:?}",
             owner.get_path(),
             data
         );
+
+        if self.state.dragging_hand_card.is_some() {
+            return;
+        }
+
+        let slot = match self.slot_ref_at_path(&data.to_node_path().to_string()) {
+            Ok(slot) => slot,
+            Err(e) => {
+                warn!("Ignoring click on board slot: {}", e);
+                return;
+            }
+        };
+
+        if let Some(card_path) = self.state.selected_hand_card.take() {
+            self.state.card_to_summon = Some((slot, card_path));
+        }
+    }
+
+    /// Invoked by a signal whenever a card in the player's hand is clicked without dragging.
+    /// The card becomes selected, ready to be placed by clicking a board slot.
+    #[export]
+    fn on_hand_card_clicked(&mut self, _owner: TRef<Node>, clicked_card_path: Variant) {
+        if self.state.dragging_hand_card.is_some() {
+            return;
+        }
+
+        let clicked_card_path = clicked_card_path.to_node_path();
+        info!("World selecting hand card: {:?}", clicked_card_path);
+        self.state.selected_hand_card = Some(clicked_card_path);
+        self.state.focus = Focus::Unfocused;
     }
 
     /// Invoked by a signal whenever a card in the player's hand begins or ends dragging.
@@ -410,6 +1142,8 @@ impl World {
 
         if is_ended {
             self.state.dragging_hand_card = None;
+            self.clear_slot_highlights();
+            self.clear_drop_preview();
             info!("World cleared dragged card.");
             let mouse_pos = mouse_pos_2d.to_vector2();
             if let Some(slot_path) = self.find_overlapping_boardslot(owner, mouse_pos) {
@@ -420,12 +1154,95 @@ impl World {
         } else {
             info!("World storing new dragged card: {:?}", dragged_card_path);
             self.state.dragging_hand_card = Some(dragged_card_path);
+            self.state.inspecting = None;
+            self.state.is_inspect_stale = true;
+            self.state.selected_hand_card = None;
+            self.state.focus = Focus::Unfocused;
+        }
+    }
+
+    /// Invoked by a signal whenever the player abandons dragging a card from their hand.
+    #[export]
+    fn on_hand_card_drag_cancelled(
+        &mut self,
+        _owner: TRef<Node>,
+        cancelled_card_path: Variant,
+    ) {
+        info!("World clearing dragged card after cancel: {:?}", cancelled_card_path);
+        self.state.dragging_hand_card = None;
+        self.clear_slot_highlights();
+        self.clear_drop_preview();
+    }
+
+    /// Invoked by a signal whenever the player starts or stops inspecting a card in their hand.
+    #[export]
+    fn on_hand_card_inspect_requested(
+        &mut self,
+        _owner: TRef<Node>,
+        card_path: Variant,
+        is_inspecting: Variant,
+    ) {
+        let target = InspectTarget::HandCard(card_path.to_node_path().to_string());
+        self.set_inspect_target(target, is_inspecting.to_bool());
+    }
+
+    /// Invoked by a signal whenever the player starts or stops inspecting a creature on the board.
+    #[export]
+    fn on_creature_inspect_requested(
+        &mut self,
+        _owner: TRef<Node>,
+        slot_path: Variant,
+        is_inspecting: Variant,
+    ) {
+        let target = InspectTarget::Creature(slot_path.to_node_path().to_string());
+        self.set_inspect_target(target, is_inspecting.to_bool());
+    }
+
+    /// Switches every label to another language, e.g. from a settings menu.
+    /// The text is redrawn on the next frame.
+    #[export]
+    fn set_language(&mut self, _owner: TRef<Node>, language: String) {
+        if !locale::set_language(&language) {
+            warn!("No string table for language {}", language);
+        }
+    }
+
+    /// Covers the game with a report of a panic, which may have left it in an unusable state.
+    /// Only the first report is shown; later panics are usually caused by the first.
+    fn show_fatal_error(&mut self, report: &PanicReport, owner: TRef<Node>) {
+        if self.state.has_fatal_error {
+            return;
+        }
+
+        self.state.has_fatal_error = true;
+        self.state.card_to_summon = None;
+
+        FatalErrorScreen::show(owner, report);
+    }
+
+    /// Changes which log messages are shown, e.g. `saltgui::agent=debug,info`.
+    #[export]
+    fn set_log_filter(&mut self, _owner: TRef<Node>, spec: String) {
+        GodotLog::set_filter(&spec);
+        info!("Log filter set to {:?}", spec);
+    }
+
+    /// Changes which log messages are written to files in `user://logs`; empty stops writing.
+    #[export]
+    fn set_file_log_filter(&mut self, _owner: TRef<Node>, spec: String) {
+        match GodotLog::set_file_filter(&spec) {
+            Ok(()) => info!("File log filter set to {:?}", spec),
+            Err(e) => warn!("Could not open the log file: {}", e),
         }
     }
 
     #[export]
     fn on_end_turn_clicked(&self, _owner: TRef<Node>) {
         info!("The world sees taht end turn was clicked.");
+        self.request_end_turn();
+    }
+
+    fn request_end_turn(&self) {
         self.message_channel
             .send_blocking(FromGui::EndTurnAction)
             .unwrap();
@@ -434,7 +1251,11 @@ impl World {
     fn connect_end_turn_clicked(&self, owner: TRef<Node>) {
         // let hand = self.player_hand(owner).unwrap();
         let button: RefInstance<EndTurnButton, Shared> =
-            util::get_as(END_TURN_BUTTON, owner).expect("Could not find end turn button node.");
+            if let Some(button) = util::get_as(END_TURN_BUTTON, owner).or_report() {
+                button
+            } else {
+                return;
+            };
 
         util::connect_signal(
             &*button.base(),
@@ -447,30 +1268,39 @@ impl World {
     fn connect_boardslot_signals(&self, owner: TRef<Node>) {
         info!("Looking for boardslot children of {:?}", owner.get_path());
 
-        let board = self.board();
-
-        for slot_index in 1..=BOARD_SLOT_COUNT {
-            let path = format!("{}{}", BOARD_SLOT_PATH_PREFIX, slot_index);
-            if let Some(slot_node) = board.base().get_node(&path) {
-                let slot_node = unsafe { slot_node.assume_safe() };
-                info!("Found board slot {:?}", slot_node.get_path());
-
-                // Connect to all boardslots.
-                util::connect_signal(
-                    &*slot_node,
-                    CLICK_RELEASED_SIGNAL,
-                    owner,
-                    "on_boardslot_click_released",
-                );
+        for slot in self.board_slots() {
+            let slot_node = if let Some(slot_node) = slot.try_resolve_instance().or_report() {
+                slot_node
             } else {
-                warn!("Could not find slot node {:?}", path);
-            }
+                continue;
+            };
+            let slot_node = slot_node.base();
+            info!("Found board slot {:?}", slot_node.get_path());
+
+            // Connect to all boardslots.
+            util::connect_signal(
+                &*slot_node,
+                CLICK_RELEASED_SIGNAL,
+                owner,
+                "on_boardslot_click_released",
+            );
+
+            util::connect_signal(
+                &*slot_node,
+                CREATURE_INSPECT_REQUESTED_SIGNAL,
+                owner,
+                "on_creature_inspect_requested",
+            );
         }
     }
 
     fn connect_hand_card_dragged(&self, owner: TRef<Node>) {
         // let hand = self.player_hand(owner).unwrap();
-        let hand = self.player_hand.resolve_instance();
+        let hand = if let Some(hand) = self.player_hand.try_resolve_instance().or_report() {
+            hand
+        } else {
+            return;
+        };
         let hand = hand.base();
 
         util::connect_signal(
@@ -479,6 +1309,27 @@ impl World {
             owner,
             "on_hand_card_dragged",
         );
+
+        util::connect_signal(
+            &*hand,
+            PLAYER_HAND_CARD_DRAG_CANCELLED,
+            owner,
+            "on_hand_card_drag_cancelled",
+        );
+
+        util::connect_signal(
+            &*hand,
+            PLAYER_HAND_CARD_CLICKED,
+            owner,
+            "on_hand_card_clicked",
+        );
+
+        util::connect_signal(
+            &*hand,
+            PLAYER_HAND_CARD_INSPECT_REQUESTED,
+            owner,
+            "on_hand_card_inspect_requested",
+        );
     }
 
     fn find_overlapping_boardslot(
@@ -487,7 +1338,7 @@ impl World {
         mouse_pos: Vector2,
     ) -> Option<NodeRef<BoardSlot, Spatial>> {
         // Cast ray from the moust position to the BoardSlot layer.
-        let camera = self.camera(owner).unwrap();
+        let camera = self.camera(owner).or_report()?;
 
         let project_from = camera.project_ray_origin(mouse_pos);
         let project_to = project_from + (camera.project_ray_normal(mouse_pos) * 10.);
@@ -511,17 +1362,13 @@ impl World {
         collision
             .get("collider")
             .try_to_object::<Area>()
-            .map(|area| {
+            .and_then(|area| {
                 let area = unsafe { area.assume_safe() };
                 let parent = area.get_parent().unwrap();
                 let parent = unsafe { parent.assume_safe() };
                 let parent_path = parent.get_path();
 
-                info!("NodeRef creating from path: {:?}", parent_path);
-
-                let node_ref: NodeRef<BoardSlot, Spatial> =
-                    NodeRef::from_parent_ref(parent_path.to_string(), owner);
-                node_ref
+                self.slot_ref_at_path(&parent_path.to_string()).ok()
             })
     }
 }
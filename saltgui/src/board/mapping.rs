@@ -0,0 +1,184 @@
+//! Conversions between the GUI's player-agnostic `SlotPos`, the engine's `BoardPos`,
+//! and the index of a slot in the scene (`BoardSlot{index + 1}`).
+//!
+//! Scene indices count the row slots in display order (see `BoardShape::positions`),
+//! followed by the opponent's hero and then the player's.
+
+use super::BoardShape;
+use crate::board_slot::SlotPos;
+use salt_engine::game_state::{
+    board::{BoardPos, RowId},
+    PlayerId,
+};
+
+/// The engine position of a slot, given which player is us and which is the opponent.
+pub(crate) fn to_board_pos(
+    pos: SlotPos,
+    player_id: PlayerId,
+    opponent_id: PlayerId,
+) -> BoardPos {
+    let owner = if pos.is_friendly {
+        player_id
+    } else {
+        opponent_id
+    };
+
+    BoardPos::new(owner, pos.row_id, pos.index)
+}
+
+/// The slot for an engine position, seen from the given player's side.
+pub(crate) fn from_board_pos(pos: &BoardPos, player_id: PlayerId) -> SlotPos {
+    SlotPos {
+        is_friendly: pos.player_id == player_id,
+        row_id: pos.row_id,
+        index: pos.row_index,
+    }
+}
+
+/// The scene index of a slot, or `None` if the board has no such slot.
+pub(crate) fn to_slot_index(shape: &BoardShape, pos: SlotPos) -> Option<usize> {
+    let row_slot_count = shape.display_rows().len() * shape.slots_per_row();
+
+    if pos.row_id == RowId::Hero {
+        return match (pos.index, pos.is_friendly) {
+            (0, false) => Some(row_slot_count),
+            (0, true) => Some(row_slot_count + 1),
+            _ => None,
+        };
+    }
+
+    if pos.index >= shape.slots_per_row() {
+        return None;
+    }
+
+    let row = shape
+        .display_rows()
+        .iter()
+        .position(|&(is_friendly, row_id)| is_friendly == pos.is_friendly && row_id == pos.row_id)?;
+
+    Some(row * shape.slots_per_row() + pos.index)
+}
+
+/// The slot at a scene index, or `None` if the index is past the end of the board.
+pub(crate) fn from_slot_index(shape: &BoardShape, index: usize) -> Option<SlotPos> {
+    let display_rows = shape.display_rows();
+    let row_slot_count = display_rows.len() * shape.slots_per_row();
+
+    if index >= row_slot_count {
+        let is_friendly = match index - row_slot_count {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+
+        return Some(SlotPos {
+            is_friendly,
+            row_id: RowId::Hero,
+            index: 0,
+        });
+    }
+
+    let (is_friendly, row_id) = display_rows[index / shape.slots_per_row()];
+
+    Some(SlotPos {
+        is_friendly,
+        row_id,
+        index: index % shape.slots_per_row(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes() -> Vec<BoardShape> {
+        let row_sets = vec![
+            vec![RowId::FrontRow],
+            vec![RowId::FrontRow, RowId::BackRow],
+        ];
+
+        row_sets
+            .into_iter()
+            .flat_map(|rows| {
+                (1..=8).map(move |slots_per_row| BoardShape::new(rows.clone(), slots_per_row))
+            })
+            .collect()
+    }
+
+    fn all_positions(shape: &BoardShape) -> Vec<SlotPos> {
+        let heroes = [false, true].iter().map(|&is_friendly| SlotPos {
+            is_friendly,
+            row_id: RowId::Hero,
+            index: 0,
+        });
+
+        shape.positions().into_iter().chain(heroes).collect()
+    }
+
+    #[test]
+    fn board_pos_round_trips() {
+        let player_id = PlayerId::new();
+        let opponent_id = PlayerId::new();
+
+        for shape in shapes() {
+            for pos in all_positions(&shape) {
+                let board_pos = to_board_pos(pos, player_id, opponent_id);
+                let expected_owner = if pos.is_friendly {
+                    player_id
+                } else {
+                    opponent_id
+                };
+
+                assert_eq!(board_pos.player_id, expected_owner);
+                assert_eq!(from_board_pos(&board_pos, player_id), pos);
+            }
+        }
+    }
+
+    #[test]
+    fn slot_index_round_trips() {
+        for shape in shapes() {
+            let positions = all_positions(&shape);
+
+            for (expected_index, &pos) in positions.iter().enumerate() {
+                let index = to_slot_index(&shape, pos).unwrap();
+
+                assert_eq!(index, expected_index, "{:?} in {:?}", pos, shape);
+                assert_eq!(from_slot_index(&shape, index), Some(pos));
+            }
+
+            assert_eq!(from_slot_index(&shape, positions.len()), None);
+        }
+    }
+
+    #[test]
+    fn positions_off_the_board_have_no_index() {
+        for shape in shapes() {
+            let past_row_end = SlotPos {
+                is_friendly: true,
+                row_id: RowId::FrontRow,
+                index: shape.slots_per_row(),
+            };
+            let second_hero = SlotPos {
+                is_friendly: true,
+                row_id: RowId::Hero,
+                index: 1,
+            };
+
+            assert_eq!(to_slot_index(&shape, past_row_end), None);
+            assert_eq!(to_slot_index(&shape, second_hero), None);
+        }
+    }
+
+    #[test]
+    fn missing_rows_have_no_index() {
+        let shape = BoardShape::new(vec![RowId::FrontRow], 6);
+        let back_row = SlotPos {
+            is_friendly: false,
+            row_id: RowId::BackRow,
+            index: 0,
+        };
+
+        assert_eq!(to_slot_index(&shape, back_row), None);
+    }
+}
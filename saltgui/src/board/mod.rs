@@ -0,0 +1,260 @@
+pub(crate) mod mapping;
+mod shape;
+
+pub(crate) use shape::BoardShape;
+
+use crate::{
+    board_slot::{BoardSlot, SlotPos},
+    error::{GuiError, OrReport},
+    util::NodeRef,
+};
+use gdnative::prelude::*;
+use log::{info, warn};
+use salt_engine::game_state::UnitCardInstancePlayerView;
+use std::collections::HashMap;
+use thiserror::Error;
+
+const BOARD_SLOT_PATH_PREFIX: &str = "BoardSlot";
+
+/// Horizontal distance between the centers of neighbouring slots.
+const SLOT_SPACING_X: f32 = 1.75;
+
+/// Vertical distance between the centers of neighbouring rows.
+const SLOT_SPACING_Y: f32 = 2.25;
+
+/// Extra space between the opponent's rows and the player's.
+const SIDE_GAP: f32 = 0.5;
+
+/// Why a slot couldn't be found on the `Board`.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub(crate) enum BoardError {
+    /// The board's slots haven't been built yet.
+    #[error("the board has not been built yet")]
+    NotBuilt,
+
+    /// The board has no slot at this position.
+    #[error("the board has no slot at {0:?}")]
+    UnknownSlot(SlotPos),
+
+    /// No slot on the board has this node path.
+    #[error("the board has no slot at path {0}")]
+    UnknownPath(String),
+}
+
+#[derive(NativeClass, Debug, Default)]
+#[inherit(Spatial)]
+pub struct Board {
+    slots: HashMap<SlotPos, NodeRef<BoardSlot, Spatial>>,
+
+    /// The shape the slots were built from, once the first game state has arrived.
+    shape: Option<BoardShape>,
+}
+
+impl Board {
+    fn new(owner: TRef<Spatial>) -> Self {
+        // let slot = NodeRef::<BoardSlot, Spatial>::from_parent_ref("Test", owner.upcast::<Node>());
+        Self {
+            slots: HashMap::new(),
+            shape: None,
+        }
+    }
+
+    pub(crate) fn shape(&self) -> Option<&BoardShape> {
+        self.shape.as_ref()
+    }
+
+    /// A reference to every slot on the board, row by row from the top of the screen.
+    pub(crate) fn slots(&self) -> Vec<NodeRef<BoardSlot, Spatial>> {
+        let shape = if let Some(shape) = &self.shape {
+            shape
+        } else {
+            return vec![];
+        };
+
+        shape
+            .positions()
+            .into_iter()
+            .filter_map(|pos| self.slots.get(&pos).cloned())
+            .collect()
+    }
+
+    /// A reference to the slot at the given position.
+    pub(crate) fn slot_at(
+        &self,
+        pos: SlotPos,
+    ) -> Result<NodeRef<BoardSlot, Spatial>, BoardError> {
+        if self.shape.is_none() {
+            return Err(BoardError::NotBuilt);
+        }
+
+        self.slots
+            .get(&pos)
+            .cloned()
+            .ok_or(BoardError::UnknownSlot(pos))
+    }
+
+    /// A reference to the slot with the given node path, e.g. from a slot's signal.
+    pub(crate) fn slot_at_path(
+        &self,
+        path: &str,
+    ) -> Result<NodeRef<BoardSlot, Spatial>, BoardError> {
+        if self.shape.is_none() {
+            return Err(BoardError::NotBuilt);
+        }
+
+        self.slots
+            .values()
+            .find(|slot| slot.path() == path)
+            .cloned()
+            .ok_or_else(|| BoardError::UnknownPath(path.to_string()))
+    }
+
+    /// Instances a `BoardSlot` for every position in the given shape, laid out in a grid
+    /// with the opponent's rows on top. Does nothing if the board is already built.
+    pub(crate) fn build(&mut self, shape: BoardShape, owner: TRef<Spatial>) {
+        if self.shape.is_some() {
+            warn!("Board was already built; ignoring new shape {:?}", shape);
+            return;
+        }
+
+        info!("Building board slots for shape: {:?}", shape);
+        remove_scene_slots(owner);
+
+        let display_rows = shape.display_rows();
+        let center_row = (display_rows.len().saturating_sub(1)) as f32 / 2.;
+        let center_index = (shape.slots_per_row().saturating_sub(1)) as f32 / 2.;
+
+        for pos in shape.positions() {
+            let row = display_rows.iter().position(|&(is_friendly, row_id)| {
+                is_friendly == pos.is_friendly && row_id == pos.row_id
+            });
+            let located = row
+                .zip(mapping::to_slot_index(&shape, pos))
+                .ok_or_else(|| GuiError::from(BoardError::UnknownSlot(pos)));
+
+            let (row, slot_index) = if let Some(located) = located.or_report() {
+                located
+            } else {
+                continue;
+            };
+
+            let side_offset = if pos.is_friendly {
+                -SIDE_GAP / 2.
+            } else {
+                SIDE_GAP / 2.
+            };
+
+            let translation = Vector3::new(
+                (pos.index as f32 - center_index) * SLOT_SPACING_X,
+                (center_row - row as f32) * SLOT_SPACING_Y + side_offset,
+                0.,
+            );
+
+            // In the Godot world, slots begin at index 1 instead of 0
+            let slot_name = format!("{}{}", BOARD_SLOT_PATH_PREFIX, slot_index + 1);
+
+            let slot = if let Some(slot) = BoardSlot::new_instance().or_report() {
+                slot
+            } else {
+                continue;
+            };
+            let initialized = slot
+                .map_mut(|s, n| {
+                    s.set_pos(pos);
+                    n.set_name(&slot_name);
+                    n.set_translation(translation);
+                })
+                .map_err(|e| GuiError::borrowed::<BoardSlot>(&slot_name, e));
+
+            if initialized.or_report().is_none() {
+                slot.into_base().free();
+                continue;
+            }
+
+            let slot = slot.into_base().into_shared();
+            owner.add_child(slot, false);
+
+            let slot_path = unsafe { slot.assume_safe() }.get_path();
+            self.slots.insert(
+                pos,
+                NodeRef::from_existing(slot_path.to_string(), slot.upcast::<Node>()),
+            );
+        }
+
+        self.shape = Some(shape);
+
+        info!("Done building {} board slots.", self.slots.len());
+    }
+
+    pub fn add_card(&mut self, card: &UnitCardInstancePlayerView, owner: TRef<Spatial>) {
+        // info!(
+        //     "Hand is receiving a card: {} {:?}",
+        //     card.definition().title(),
+        //     card.id()
+        // );
+
+        // let card_instance = CardInstance::new_instance();
+
+        // // let offset = hand.map(|n, _| n.hand_len).unwrap() as f32 * OFFSET_DIST_MULTIPLIER;
+        // let offset = self.hand_len as f32 * OFFSET_DIST_MULTIPLIER;
+
+        // card_instance
+        //     .map_mut(|c, n| {
+        //         let def = card.definition();
+        //         c.set_title(def.title());
+        //         c.set_body(def.text());
+
+        //         c.set_view(card.clone());
+
+        //         n.translate(Vector3::new(offset, 0., 0.));
+
+        //         util::connect_signal(n, CARD_DRAGGED, owner, "on_card_dragged");
+        //     })
+        //     .unwrap();
+
+        // self.hand_len += 1;
+
+        // let card_instance = card_instance.into_base();
+        // let card_instance = card_instance.into_shared();
+        // owner.add_child(card_instance, false);
+
+        // let card_instance = unsafe { card_instance.assume_safe() };
+        // let card_path = card_instance.get_path();
+
+        // owner.emit_signal(PLAYER_HAND_CARD_ADDED_SIGNAL, &[card_path.to_variant()]);
+
+        // info!("Added card {:?} to PlayerHand.", card_path);
+    }
+}
+
+/// Removes any slots placed in the scene by hand, which would otherwise sit under the built
+/// ones and take their names.
+fn remove_scene_slots(owner: TRef<Spatial>) {
+    let placed = owner
+        .get_children()
+        .iter()
+        .filter_map(|child| child.try_to_object::<Node>())
+        .filter(|child| {
+            let name = unsafe { child.assume_safe() }.name().to_string();
+            name.starts_with(BOARD_SLOT_PATH_PREFIX)
+        })
+        .collect::<Vec<_>>();
+
+    if placed.is_empty() {
+        return;
+    }
+
+    warn!(
+        "Removing {} board slot(s) placed in the scene; the board builds its own",
+        placed.len()
+    );
+
+    for slot in placed {
+        let slot = unsafe { slot.assume_safe() };
+        owner.remove_child(slot);
+        slot.queue_free();
+    }
+}
+
+#[methods]
+impl Board {}
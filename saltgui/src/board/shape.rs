@@ -0,0 +1,72 @@
+use crate::board_slot::SlotPos;
+use salt_engine::game_state::{board::RowId, GameStatePlayerView, PlayerId};
+
+/// The rows and slots on each side of the board, as dictated by the engine.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BoardShape {
+    /// One side's rows, from the center of the board outward.
+    rows: Vec<RowId>,
+    slots_per_row: usize,
+}
+
+impl BoardShape {
+    /// A shape with the given rows per side, listed from the center of the board outward.
+    pub(crate) fn new(rows: Vec<RowId>, slots_per_row: usize) -> Self {
+        Self {
+            rows,
+            slots_per_row,
+        }
+    }
+
+    /// Reads the shape of the player's side of the board, which the opponent's mirrors.
+    pub(crate) fn from_state(state: &GameStatePlayerView, player_id: PlayerId) -> Self {
+        let side = state.board().player_side(player_id);
+
+        let mut rows = Vec::new();
+        for slot in side {
+            let row_id = slot.pos().row_id;
+            if row_id != RowId::Hero && !rows.contains(&row_id) {
+                rows.push(row_id);
+            }
+        }
+
+        rows.sort_by_key(|&row_id| row_id != RowId::FrontRow);
+
+        let slots_per_row = rows
+            .first()
+            .map_or(0, |&row_id| side.iter().filter(|s| s.pos().row_id == row_id).count());
+
+        Self::new(rows, slots_per_row)
+    }
+
+    pub(crate) fn rows_per_side(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub(crate) fn slots_per_row(&self) -> usize {
+        self.slots_per_row
+    }
+
+    /// Every row on the board, from the top of the screen to the bottom:
+    /// the opponent's rows from the back forward, then the player's from the front back.
+    pub(crate) fn display_rows(&self) -> Vec<(bool, RowId)> {
+        let enemy_rows = self.rows.iter().rev().map(|&row_id| (false, row_id));
+        let friendly_rows = self.rows.iter().map(|&row_id| (true, row_id));
+
+        enemy_rows.chain(friendly_rows).collect()
+    }
+
+    /// Every slot position on the board, row by row from the top of the screen, left to right.
+    pub(crate) fn positions(&self) -> Vec<SlotPos> {
+        self.display_rows()
+            .into_iter()
+            .flat_map(|(is_friendly, row_id)| {
+                (0..self.slots_per_row).map(move |index| SlotPos {
+                    is_friendly,
+                    row_id,
+                    index,
+                })
+            })
+            .collect()
+    }
+}
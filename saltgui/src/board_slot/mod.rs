@@ -1,19 +1,23 @@
 use crate::{
     card_board_instance::CardBoardInstance,
-    textbox::TextBox,
+    error::{GuiError, OrReport},
+    input, scene_cache,
+    scene_contract::{RequiredNode, SceneContract},
+    textbox::{self, TextBox},
     util::{self, NodeRef},
-    SignalName,
+    SignalName, INPUT_EVENT_SIGNAL, MOUSE_ENTERED_SIGNAL, MOUSE_EXITED_SIGNAL,
 };
-use gdnative::{api::InputEventMouseButton, prelude::*};
-use log::info;
+use gdnative::{
+    api::{Area, MeshInstance, SpatialMaterial},
+    prelude::*,
+};
+use log::{info, warn};
 use salt_engine::{
     cards::UnitCardDefinitionView,
     game_logic::events::CreatureSetClientEvent,
-    game_state::{
-        board::{BoardPos, RowId},
-        PlayerId,
-    },
+    game_state::{board::RowId, UnitCardInstanceId},
 };
+use std::hash::{Hash, Hasher};
 
 #[derive(NativeClass, Debug)]
 #[register_with(Self::register)]
@@ -21,13 +25,48 @@ use salt_engine::{
 pub struct BoardSlot {
     textbox: NodeRef<TextBox, Spatial>,
     board_pos: Option<SlotPos>,
+    creature: Option<(UnitCardInstanceId, Ref<Spatial, Shared>)>,
+    highlight: SlotHighlight,
+    highlight_quad: Option<(Ref<MeshInstance, Shared>, Ref<SpatialMaterial, Shared>)>,
 }
 
+pub(crate) const BOARD_SLOT_SCENE: &str = "res://board_slot/board_slot.tscn";
+const TEXT_BOX_PATH: &str = "TextBox";
+const COLLIDER_PATH: &str = "Area";
+
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "BoardSlot",
+    scene: Some(BOARD_SLOT_SCENE),
+    nodes: &[
+        RequiredNode::new(TEXT_BOX_PATH, "Spatial")
+            .with_script("TextBox")
+            .containing(&textbox::SCENE_CONTRACT),
+        RequiredNode::new(COLLIDER_PATH, "Area"),
+    ],
+};
+
+const HIGHLIGHT_NAME: &str = "Highlight";
+const HIGHLIGHT_SIZE: (f32, f32) = (1.5, 2.);
+
+/// Drawn just in front of the slot so it isn't hidden by the slot's own mesh.
+const HIGHLIGHT_OFFSET: f32 = 0.01;
+
 /// Emitted when a click is released over this `BoardSlot`.
 pub(crate) const CLICK_RELEASED_SIGNAL: SignalName = SignalName("click_released");
 
-/// Internal signal from Godot emitted when there is an input event.
-pub(crate) const INPUT_EVENT_SIGNAL: SignalName = SignalName("input_event");
+/// Emitted when a creature is placed in this empty `BoardSlot`.
+pub(crate) const CREATURE_PLACED_SIGNAL: SignalName = SignalName("creature_placed");
+
+/// Emitted when the creature in this `BoardSlot` is swapped for another.
+pub(crate) const CREATURE_REPLACED_SIGNAL: SignalName = SignalName("creature_replaced");
+
+/// Emitted when the creature in this `BoardSlot` is removed, leaving it empty.
+pub(crate) const CREATURE_CLEARED_SIGNAL: SignalName = SignalName("creature_cleared");
+
+/// Emitted when the pointer starts or stops hovering this slot's creature,
+/// or `input::INSPECT` is pressed over the creature.
+pub(crate) const CREATURE_INSPECT_REQUESTED_SIGNAL: SignalName =
+    SignalName("creature_inspect_requested");
 
 /// Just like `BoardSlot`, except agnostic to the player's ID.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -37,26 +76,165 @@ pub struct SlotPos {
     pub index: usize,
 }
 
-impl SlotPos {
-    pub fn into_board_slot(self, player_id: PlayerId) -> BoardPos {
-        BoardPos::new(player_id, self.row_id, self.index)
+impl Eq for SlotPos {}
+
+impl Hash for SlotPos {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let row = match self.row_id {
+            RowId::FrontRow => 0,
+            RowId::BackRow => 1,
+            RowId::Hero => 2,
+        };
+
+        self.is_friendly.hash(state);
+        row.hash(state);
+        self.index.hash(state);
+    }
+}
+
+/// How a `BoardSlot` is drawn to guide the player, e.g. while dragging a card.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SlotHighlight {
+    /// Drawn normally.
+    Normal,
+
+    /// The dragged card can be summoned here.
+    Valid,
+
+    /// The slot being pointed at: under the cursor while a summonable card is dragged,
+    /// or holding the keyboard or gamepad focus, with or without a card.
+    Hover,
+
+    /// The dragged card can't be summoned here.
+    Invalid,
+}
+
+impl SlotHighlight {
+    fn color(self) -> Option<Color> {
+        match self {
+            SlotHighlight::Normal => None,
+            SlotHighlight::Valid => Some(Color::rgba(0.3, 0.9, 0.4, 0.3)),
+            SlotHighlight::Hover => Some(Color::rgba(0.5, 1., 0.6, 0.7)),
+            SlotHighlight::Invalid => Some(Color::rgba(0., 0., 0., 0.5)),
+        }
     }
 }
 
 impl BoardSlot {
     fn new(_owner: &Spatial) -> Self {
         Self {
-            textbox: NodeRef::from_path("TextBox"),
+            textbox: NodeRef::from_path(TEXT_BOX_PATH),
             board_pos: None,
+            creature: None,
+            highlight: SlotHighlight::Normal,
+            highlight_quad: None,
+        }
+    }
+
+    /// Puts a creature in this slot, if it's empty.
+    /// Returns whether it was placed; use `replace_creature` to swap out an existing one.
+    pub fn place_creature(
+        &mut self,
+        id: UnitCardInstanceId,
+        card_instance: Instance<CardBoardInstance, Unique>,
+        owner: TRef<Spatial>,
+    ) -> bool {
+        if self.is_occupied() {
+            warn!("Cannot place creature {:?}: slot is occupied", id);
+            return false;
+        }
+
+        self.attach_creature(id, card_instance, owner);
+        owner.emit_signal(CREATURE_PLACED_SIGNAL, &[owner.get_path().to_variant()]);
+
+        true
+    }
+
+    /// Puts a creature in this slot, freeing whichever creature was there before.
+    pub fn replace_creature(
+        &mut self,
+        id: UnitCardInstanceId,
+        card_instance: Instance<CardBoardInstance, Unique>,
+        owner: TRef<Spatial>,
+    ) {
+        if !self.is_occupied() {
+            self.place_creature(id, card_instance, owner);
+            return;
+        }
+
+        self.detach_creature();
+        self.attach_creature(id, card_instance, owner);
+        owner.emit_signal(CREATURE_REPLACED_SIGNAL, &[owner.get_path().to_variant()]);
+    }
+
+    /// Removes and frees the creature in this slot, if there is one.
+    pub fn clear_creature(&mut self, owner: TRef<Spatial>) {
+        if !self.is_occupied() {
+            return;
         }
+
+        self.detach_creature();
+        owner.emit_signal(CREATURE_CLEARED_SIGNAL, &[owner.get_path().to_variant()]);
     }
 
-    pub fn receive_summon_z(
-        &self,
+    fn attach_creature(
+        &mut self,
+        id: UnitCardInstanceId,
         card_instance: Instance<CardBoardInstance, Unique>,
         owner: TRef<Spatial>,
     ) {
-        owner.add_child(card_instance.into_base(), false);
+        let node = card_instance.into_base().into_shared();
+        owner.add_child(node, false);
+        self.creature = Some((id, node));
+    }
+
+    fn detach_creature(&mut self) {
+        if let Some((id, node)) = self.creature.take() {
+            info!("Freeing creature {:?} from slot {:?}", id, self.board_pos);
+            unsafe { node.assume_safe() }.queue_free();
+        }
+    }
+
+    pub fn is_occupied(&self) -> bool {
+        self.creature.is_some()
+    }
+
+    /// The id of the creature in this slot, if there is one.
+    pub fn creature_id(&self) -> Option<UnitCardInstanceId> {
+        self.creature.as_ref().map(|(id, _)| *id)
+    }
+
+    /// The node of the creature in this slot, if there is one.
+    pub fn creature_node(&self) -> Option<Ref<Spatial, Shared>> {
+        self.creature.as_ref().map(|&(_, node)| node)
+    }
+
+    pub fn highlight(&self) -> SlotHighlight {
+        self.highlight
+    }
+
+    pub fn set_highlight(&mut self, highlight: SlotHighlight) {
+        if self.highlight == highlight {
+            return;
+        }
+
+        self.highlight = highlight;
+
+        let (quad, material) = if let Some(highlight_quad) = &self.highlight_quad {
+            highlight_quad
+        } else {
+            warn!("set_highlight invoked before BoardSlot was ready");
+            return;
+        };
+
+        let quad = unsafe { quad.assume_safe() };
+
+        if let Some(color) = highlight.color() {
+            unsafe { material.assume_safe() }.set_albedo(color);
+            quad.set_visible(true);
+        } else {
+            quad.set_visible(false);
+        }
     }
 
     pub fn receive_summon(&self, card_view: CreatureSetClientEvent) {
@@ -66,12 +244,9 @@ impl BoardSlot {
 
         let text = format!("{}\n{}/{}", title, attack, health);
 
-        let textbox = self.textbox.resolve_instance();
-        textbox
-            .map_mut(|i, _| {
-                i.set_text(&text);
-            })
-            .unwrap();
+        self.textbox
+            .try_map_mut(|i, _| i.set_text(&text))
+            .or_report();
     }
 
     pub fn set_pos(&mut self, pos: SlotPos) {
@@ -82,19 +257,30 @@ impl BoardSlot {
     pub fn pos(&self) -> SlotPos {
         self.board_pos.unwrap()
     }
+
+    pub(crate) fn new_instance() -> Result<Instance<BoardSlot, Unique>, GuiError> {
+        scene_cache::instance_script(BOARD_SLOT_SCENE)
+    }
 }
 
 #[methods]
 impl BoardSlot {
     #[export]
     fn _ready(&mut self, owner: TRef<Spatial>) {
-        {
-            let mouse_collider = owner.get_node("Area").unwrap();
-            let mouse_collider = unsafe { mouse_collider.assume_safe_if_sane().unwrap() };
-            util::connect_signal(&*mouse_collider, INPUT_EVENT_SIGNAL, owner, "input_event");
+        if let Some(collider) = util::get_node::<Area>(COLLIDER_PATH, owner.upcast()).or_report() {
+            util::connect_signal(collider, INPUT_EVENT_SIGNAL, owner, "input_event");
+            util::connect_signal(collider, MOUSE_ENTERED_SIGNAL, owner, "on_mouse_entered");
+            util::connect_signal(collider, MOUSE_EXITED_SIGNAL, owner, "on_mouse_exited");
         }
 
-        self.textbox.init_from_parent_ref(owner);
+        self.textbox.try_init_from_parent_ref(owner).or_report();
+
+        self.highlight_quad = Some(util::add_overlay_quad(
+            owner,
+            HIGHLIGHT_NAME,
+            Vector2::new(HIGHLIGHT_SIZE.0, HIGHLIGHT_SIZE.1),
+            Vector3::new(0., 0., HIGHLIGHT_OFFSET),
+        ));
     }
 
     #[export]
@@ -109,23 +295,75 @@ impl BoardSlot {
         _click_normal: Variant,
         _shape_idx: Variant,
     ) {
-        if let Some(event) = mouse_event.try_to_object::<InputEventMouseButton>() {
-            let click = unsafe { event.assume_safe() };
-            if !click.is_pressed() {
+        if let Some(event) = mouse_event.try_to_object::<InputEvent>() {
+            let event = unsafe { event.assume_safe() };
+            if input::is_select_released(&event) {
                 owner.emit_signal(CLICK_RELEASED_SIGNAL, &[owner.get_path().to_variant()]);
+            } else if event.is_action_pressed(input::INSPECT, false)
+                || input::is_inspect_click(&event)
+            {
+                self.request_inspect(owner, true);
             }
         }
     }
 
+    #[export]
+    fn on_mouse_entered(&mut self, owner: TRef<Spatial>) {
+        self.request_inspect(owner, true);
+    }
+
+    #[export]
+    fn on_mouse_exited(&mut self, owner: TRef<Spatial>) {
+        self.request_inspect(owner, false);
+    }
+
+    /// Asks for this slot's creature to be shown in, or removed from, the inspect overlay.
+    /// Empty slots have nothing to show, but may still dismiss a creature that just left.
+    fn request_inspect(&self, owner: TRef<Spatial>, is_inspecting: bool) {
+        if is_inspecting && !self.is_occupied() {
+            return;
+        }
+
+        owner.emit_signal(
+            CREATURE_INSPECT_REQUESTED_SIGNAL,
+            &[owner.get_path().to_variant(), is_inspecting.to_variant()],
+        );
+    }
+
     fn register(builder: &ClassBuilder<Self>) {
+        for signal in &[
+            CLICK_RELEASED_SIGNAL,
+            CREATURE_PLACED_SIGNAL,
+            CREATURE_REPLACED_SIGNAL,
+            CREATURE_CLEARED_SIGNAL,
+        ] {
+            builder.add_signal(Signal {
+                name: signal.as_ref(),
+                args: &[SignalArgument {
+                    name: "path",
+                    default: Variant::from_str("<empty_default>"),
+                    export_info: ExportInfo::new(VariantType::GodotString),
+                    usage: PropertyUsage::DEFAULT,
+                }],
+            });
+        }
+
         builder.add_signal(Signal {
-            name: CLICK_RELEASED_SIGNAL.as_ref(),
-            args: &[SignalArgument {
-                name: "path",
-                default: Variant::from_str("<empty_default>"),
-                export_info: ExportInfo::new(VariantType::GodotString),
-                usage: PropertyUsage::DEFAULT,
-            }],
+            name: CREATURE_INSPECT_REQUESTED_SIGNAL.as_ref(),
+            args: &[
+                SignalArgument {
+                    name: "path",
+                    default: Variant::from_str("<empty_default>"),
+                    export_info: ExportInfo::new(VariantType::GodotString),
+                    usage: PropertyUsage::DEFAULT,
+                },
+                SignalArgument {
+                    name: "is_inspecting",
+                    default: Variant::from_bool(false),
+                    export_info: ExportInfo::new(VariantType::Bool),
+                    usage: PropertyUsage::DEFAULT,
+                },
+            ],
         });
     }
 }
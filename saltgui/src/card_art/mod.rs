@@ -0,0 +1,131 @@
+use gdnative::api::{File, Image, ImageTexture, ResourceLoader, Sprite3D, Texture};
+use gdnative::prelude::*;
+use log::{info, warn};
+use std::{cell::RefCell, collections::HashMap};
+
+/// Art shipped with the game, as `<art key>.<extension>`.
+const BUNDLED_ART_DIR: &str = "res://card/art";
+
+/// Art dropped in by artists, which takes precedence over the bundled art.
+const OVERRIDE_ART_DIR: &str = "user://card_art";
+
+const ART_EXTENSIONS: &[&str] = &["png", "jpg", "webp"];
+
+/// Shown for cards that have no art of their own.
+const PLACEHOLDER_ART: &str = "res://card/art/placeholder.png";
+
+const ART_SPRITE_NAME: &str = "Art";
+
+thread_local! {
+    /// Textures by art key, including `None` for cards known to have no art.
+    static ART_CACHE: RefCell<HashMap<String, Option<Ref<Texture, Shared>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The file name, without extension, that a card's art is stored under:
+/// its title in lowercase, with anything but letters and digits replaced by `_`.
+pub(crate) fn art_key(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// The art for the card with the given title, falling back to the placeholder.
+/// Textures are loaded once and cached.
+pub(crate) fn texture_for(title: &str) -> Option<Ref<Texture, Shared>> {
+    cached_texture(&art_key(title)).or_else(|| {
+        ART_CACHE.with(|cache| {
+            cache
+                .borrow_mut()
+                .entry(PLACEHOLDER_ART.to_string())
+                .or_insert_with(|| load_texture(PLACEHOLDER_ART))
+                .clone()
+        })
+    })
+}
+
+/// Adds the art for the card with the given title as a child sprite of `parent`,
+/// scaled to `height` in world units. Does nothing if there is no art, not even a placeholder.
+pub(crate) fn add_art_sprite(parent: TRef<Spatial>, title: &str, offset: Vector3, height: f32) {
+    let texture = if let Some(texture) = texture_for(title) {
+        texture
+    } else {
+        warn!("No art or placeholder art for card {}", title);
+        return;
+    };
+
+    let texture_height = unsafe { texture.assume_safe() }.get_height();
+    if texture_height <= 0 {
+        return;
+    }
+
+    let sprite = Sprite3D::new();
+    sprite.set_name(ART_SPRITE_NAME);
+    sprite.set_texture(texture);
+    sprite.set_pixel_size(f64::from(height) / texture_height as f64);
+    sprite.set_translation(offset);
+
+    parent.add_child(sprite.into_shared(), false);
+}
+
+fn cached_texture(key: &str) -> Option<Ref<Texture, Shared>> {
+    ART_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .entry(key.to_string())
+            .or_insert_with(|| find_art(key))
+            .clone()
+    })
+}
+
+/// Looks for the art in the override directory first, then among the bundled art.
+fn find_art(key: &str) -> Option<Ref<Texture, Shared>> {
+    for dir in &[OVERRIDE_ART_DIR, BUNDLED_ART_DIR] {
+        for extension in ART_EXTENSIONS {
+            let path = format!("{}/{}.{}", dir, key, extension);
+            if let Some(texture) = load_texture(&path) {
+                info!("Loaded card art: {}", path);
+                return Some(texture);
+            }
+        }
+    }
+
+    None
+}
+
+fn load_texture(path: &str) -> Option<Ref<Texture, Shared>> {
+    if path.starts_with("res://") {
+        let loader = ResourceLoader::godot_singleton();
+        if !loader.exists(path, "Texture") {
+            return None;
+        }
+
+        let texture = loader.load(path, "Texture", false)?;
+        return unsafe { texture.assume_safe() }
+            .cast::<Texture>()
+            .map(|t| t.claim());
+    }
+
+    // Files under user:// aren't imported, so they have to be read as plain images.
+    if !File::new().file_exists(path) {
+        return None;
+    }
+
+    let image = Image::new();
+    if let Err(e) = image.load(path) {
+        warn!("Could not load card art {}: {:?}", path, e);
+        return None;
+    }
+
+    let texture = ImageTexture::new();
+    texture.create_from_image(image, Texture::FLAGS_DEFAULT);
+
+    Some(texture.into_shared().upcast::<Texture>())
+}
@@ -1,22 +1,49 @@
-use crate::util::{self, NodeRef};
-use gdnative::api::RichTextLabel;
+use crate::{
+    card_art,
+    error::{GuiError, OrReport},
+    scene_cache,
+    scene_contract::{RequiredNode, SceneContract},
+    util::NodeRef,
+};
+use gdnative::api::{RichTextLabel, SpriteBase3D};
 use gdnative::prelude::*;
 use log::info;
 use salt_engine::game_state::UnitCardInstancePlayerView;
 
-const CARD_BOARD_INSTANCE_SCENE: &str = "res://card/card_board_instance/card_board_instance.tscn";
+pub(crate) const CARD_BOARD_INSTANCE_SCENE: &str =
+    "res://card/card_board_instance/card_board_instance.tscn";
 const TITLE_PATH: &str = "Title/TitleViewport/Control/Panel/RichTextLabel";
 const STATS_PATH: &str = "Stats/StatsViewport/Control/Panel/RichTextLabel";
 
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "CardBoardInstance",
+    scene: Some(CARD_BOARD_INSTANCE_SCENE),
+    nodes: &[
+        RequiredNode::new(TITLE_PATH, "RichTextLabel"),
+        RequiredNode::new(STATS_PATH, "RichTextLabel"),
+    ],
+};
+
+/// Where the card's art sits, above the stats.
+const ART_OFFSET: (f32, f32, f32) = (0., 0.2, 0.01);
+const ART_HEIGHT: f32 = 0.9;
+
+/// The opacity of a card shown as a drop preview.
+const GHOST_ALPHA: f32 = 0.45;
+
 #[derive(NativeClass)]
 #[register_with(Self::register)]
 #[inherit(Spatial)]
 pub struct CardBoardInstance {
     title_label_init: Option<String>,
+    art_title: Option<String>,
     stats_label_init: Option<String>,
     stats_label: NodeRef<RichTextLabel, Spatial>,
     title_label: NodeRef<RichTextLabel, Spatial>,
     view: Option<UnitCardInstancePlayerView>,
+
+    /// Whether the card is translucent, as a drop preview.
+    is_ghost: bool,
     target_z: f32,
     cur_direction: f32,
 }
@@ -30,33 +57,63 @@ impl CardBoardInstance {
             stats_label: NodeRef::from_path(STATS_PATH),
             title_label: NodeRef::from_path(TITLE_PATH),
             title_label_init: None,
+            art_title: None,
             stats_label_init: None,
             view: None,
+            is_ghost: false,
             target_z: MAX_Z,
             cur_direction: 1.,
         }
     }
 
     pub(crate) fn set_title(&mut self, title: impl AsRef<str>) {
-        if let Some(r) = self.title_label.try_resolve() {
+        self.art_title = Some(title.as_ref().to_string());
+
+        if let Ok(r) = self.title_label.try_resolve() {
             r.set_text(title);
         } else {
             self.title_label_init = Some(title.as_ref().to_string());
         }
     }
 
+    /// Sets the stats text, as BBCode; see `card_text::format_stats`.
     pub(crate) fn set_stats(&mut self, stats: impl AsRef<str>) {
-        if let Some(r) = self.stats_label.try_resolve() {
-            r.set_text(stats);
+        if let Ok(r) = self.stats_label.try_resolve() {
+            r.set_use_bbcode(true);
+            r.set_bbcode(stats.as_ref());
         } else {
             self.stats_label_init = Some(stats.as_ref().to_string());
         }
     }
 
-    pub(crate) fn new_instance() -> Instance<CardBoardInstance, Unique> {
-        let card_instance = util::load_scene(CARD_BOARD_INSTANCE_SCENE).unwrap();
-        let card_instance = util::instance_scene::<Spatial>(&card_instance);
-        card_instance.cast_instance::<CardBoardInstance>().unwrap()
+    pub(crate) fn set_view(&mut self, view: UnitCardInstancePlayerView) {
+        self.view = Some(view);
+    }
+
+    pub(crate) fn view(&self) -> Option<&UnitCardInstancePlayerView> {
+        self.view.as_ref()
+    }
+
+    /// Makes the card translucent, for previewing where a card will be summoned.
+    pub(crate) fn set_ghost(&mut self, is_ghost: bool, owner: TRef<Spatial>) {
+        self.is_ghost = is_ghost;
+        self.apply_ghost(owner);
+    }
+
+    /// Sets every sprite's opacity, including the art, which is only added once ready.
+    fn apply_ghost(&self, owner: TRef<Spatial>) {
+        let alpha = if self.is_ghost { GHOST_ALPHA } else { 1. };
+
+        for child in owner.get_children().iter() {
+            if let Some(sprite) = child.try_to_object::<SpriteBase3D>() {
+                let sprite = unsafe { sprite.assume_safe() };
+                sprite.set_modulate(Color::rgba(1., 1., 1., alpha));
+            }
+        }
+    }
+
+    pub(crate) fn new_instance() -> Result<Instance<CardBoardInstance, Unique>, GuiError> {
+        scene_cache::instance_script(CARD_BOARD_INSTANCE_SCENE)
     }
 }
 
@@ -68,8 +125,8 @@ impl CardBoardInstance {
             "CardBoardInstance generated under parent: {:?}",
             owner.get_path()
         );
-        self.stats_label.init_from_parent(owner);
-        self.title_label.init_from_parent(owner);
+        self.stats_label.try_init_from_parent(owner).or_report();
+        self.title_label.try_init_from_parent(owner).or_report();
 
         if let Some(init_title) = self.title_label_init.take() {
             self.set_title(init_title);
@@ -78,6 +135,17 @@ impl CardBoardInstance {
         if let Some(init_stats) = self.stats_label_init.take() {
             self.set_stats(init_stats);
         }
+
+        if let Some(art_title) = &self.art_title {
+            card_art::add_art_sprite(
+                owner,
+                art_title,
+                Vector3::new(ART_OFFSET.0, ART_OFFSET.1, ART_OFFSET.2),
+                ART_HEIGHT,
+            );
+        }
+
+        self.apply_ghost(owner);
     }
 
     // #[export]
@@ -0,0 +1,176 @@
+use crate::{card_text, locale};
+use gdnative::api::{GlobalConstants, Label, PanelContainer, RichTextLabel, VBoxContainer};
+use gdnative::prelude::*;
+use log::warn;
+use salt_engine::{cards::UnitCardDefinitionView, game_state::UnitCardInstancePlayerView};
+
+/// How wide the overlay is, in pixels; it grows downward to fit the card's text.
+const PANEL_WIDTH: f32 = 320.;
+
+/// Distance from the right edge of the screen to the overlay.
+const PANEL_MARGIN: f32 = 24.;
+
+/// Everything the inspect overlay shows about a card.
+#[derive(Debug, Clone)]
+pub(crate) struct InspectedCard {
+    pub title: String,
+    pub cost: i32,
+    pub base_cost: i32,
+    pub text: String,
+    pub attack: i32,
+    pub health: i32,
+    pub base_attack: i32,
+    pub base_health: i32,
+    pub is_friendly: bool,
+}
+
+impl InspectedCard {
+    pub(crate) fn from_view(view: &UnitCardInstancePlayerView, is_friendly: bool) -> Self {
+        let definition = view.definition();
+
+        Self {
+            title: definition.title().to_string(),
+            cost: view.cost(),
+            base_cost: definition.cost(),
+            text: definition.text().to_string(),
+            attack: view.attack(),
+            health: view.health(),
+            base_attack: definition.attack(),
+            base_health: definition.health(),
+            is_friendly,
+        }
+    }
+}
+
+/// The overlay's controls, created once the overlay is ready.
+struct Widgets {
+    panel: Ref<PanelContainer>,
+    title: Ref<Label>,
+    cost: Ref<RichTextLabel>,
+    text: Ref<RichTextLabel>,
+    stats: Ref<RichTextLabel>,
+    card_owner: Ref<Label>,
+}
+
+/// A large overlay describing one card in full, for cards too small to read in place.
+#[derive(NativeClass)]
+#[inherit(Control)]
+pub struct CardInspect {
+    widgets: Option<Widgets>,
+}
+
+impl CardInspect {
+    fn new(_owner: &Control) -> Self {
+        Self { widgets: None }
+    }
+
+    /// Fills the overlay with the given card and shows it.
+    pub(crate) fn show_card(&self, card: &InspectedCard) {
+        let widgets = if let Some(widgets) = &self.widgets {
+            widgets
+        } else {
+            warn!("show_card invoked before CardInspect was ready");
+            return;
+        };
+
+        let stats = card_text::format_stats(
+            card.attack,
+            card.health,
+            card.base_attack,
+            card.base_health,
+        );
+
+        let base_stats = locale::tr_with(
+            "inspect.base_stats",
+            &[("attack", &card.base_attack), ("health", &card.base_health)],
+        );
+
+        let cost = locale::tr_plural(
+            "inspect.cost",
+            i64::from(card.cost),
+            &[("cost", &card_text::format_cost(card.cost, card.base_cost))],
+        );
+
+        let owner_text = locale::tr(if card.is_friendly {
+            "inspect.owner.friendly"
+        } else {
+            "inspect.owner.enemy"
+        });
+
+        unsafe {
+            widgets.title.assume_safe().set_text(&card.title);
+            widgets.cost.assume_safe().set_bbcode(cost);
+            widgets
+                .text
+                .assume_safe()
+                .set_bbcode(card_text::format_card_text(&card.text));
+            widgets
+                .stats
+                .assume_safe()
+                .set_bbcode(format!("{}\n{}", stats, base_stats));
+            widgets.card_owner.assume_safe().set_text(owner_text);
+            widgets.panel.assume_safe().set_visible(true);
+        }
+    }
+
+    pub(crate) fn hide_card(&self) {
+        if let Some(widgets) = &self.widgets {
+            unsafe { widgets.panel.assume_safe() }.set_visible(false);
+        }
+    }
+}
+
+#[methods]
+impl CardInspect {
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        // The overlay only displays; clicks pass through to the board underneath.
+        owner.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+
+        let panel = PanelContainer::new();
+        panel.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+        panel.set_anchors_preset(Control::PRESET_CENTER_RIGHT, false);
+        panel.set_custom_minimum_size(Vector2::new(PANEL_WIDTH, 0.));
+        panel.set_margin(
+            GlobalConstants::MARGIN_LEFT,
+            -f64::from(PANEL_WIDTH + PANEL_MARGIN),
+        );
+        panel.set_margin(GlobalConstants::MARGIN_RIGHT, -f64::from(PANEL_MARGIN));
+        panel.set_visible(false);
+
+        let rows = VBoxContainer::new();
+
+        let title = Label::new().into_shared();
+        let cost = rich_text_label().into_shared();
+        let text = rich_text_label().into_shared();
+        let stats = rich_text_label().into_shared();
+        let card_owner = Label::new().into_shared();
+
+        rows.add_child(title, false);
+        rows.add_child(cost, false);
+        rows.add_child(text, false);
+        rows.add_child(stats, false);
+        rows.add_child(card_owner, false);
+        panel.add_child(rows, false);
+
+        let panel = panel.into_shared();
+        owner.add_child(panel, false);
+
+        self.widgets = Some(Widgets {
+            panel,
+            title,
+            cost,
+            text,
+            stats,
+            card_owner,
+        });
+    }
+}
+
+fn rich_text_label() -> Ref<RichTextLabel, Unique> {
+    let label = RichTextLabel::new();
+    label.set_use_bbcode(true);
+    label.set_fit_content_height(true);
+    label.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+    label
+}
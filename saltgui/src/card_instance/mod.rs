@@ -1,19 +1,81 @@
-use crate::textbox::TextBox;
+use crate::card_art;
+use crate::card_text;
+use crate::error::{GuiError, OrReport};
+use crate::hand::CardLayout;
+use crate::input;
+use crate::scene_cache;
+use crate::scene_contract::{RequiredNode, SceneContract};
+use crate::textbox::{self, TextBox};
 use crate::util::NodeRef;
-use crate::{util, SignalName};
-use gdnative::api::InputEventMouseButton;
+use crate::{util, SignalName, INPUT_EVENT_SIGNAL, MOUSE_ENTERED_SIGNAL, MOUSE_EXITED_SIGNAL};
+use gdnative::api::{
+    InputEventMouse, InputEventScreenDrag, InputEventScreenTouch, MeshInstance, RichTextLabel,
+    SpatialMaterial, StaticBody, OS,
+};
 use gdnative::prelude::*;
-use log::info;
+use log::{info, warn};
 use salt_engine::cards::UnitCardDefinitionView;
 use salt_engine::game_state::UnitCardInstancePlayerView;
 
-const CARD_INSTANCE_SCENE: &str = "res://card/creature_instance.tscn";
+pub(crate) const CARD_INSTANCE_SCENE: &str = "res://card/creature_instance.tscn";
 const BODY_TEXT_LABEL: &str = "CardBodyText/Viewport/GUI/Panel/RichTextLabel";
 const TITLE_TEXT_LABEL: &str = "CardTitleText/Viewport/GUI/Panel/RichTextLabel";
 const COST_LABEL: &str = "Cost";
+const COLLIDER_PATH: &str = "StaticBody";
+
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "CardInstance",
+    scene: Some(CARD_INSTANCE_SCENE),
+    nodes: &[
+        RequiredNode::new(BODY_TEXT_LABEL, "RichTextLabel"),
+        RequiredNode::new(TITLE_TEXT_LABEL, "RichTextLabel"),
+        RequiredNode::new(COST_LABEL, "Spatial")
+            .with_script("TextBox")
+            .containing(&textbox::SCENE_CONTRACT),
+        RequiredNode::new(COLLIDER_PATH, "StaticBody"),
+    ],
+};
+
+/// How quickly a card eases into its layout position; higher is snappier.
+const LAYOUT_EASE_SPEED: f32 = 10.;
+
+/// Once a card is this close to its layout position, it snaps into place.
+const LAYOUT_SNAP_DISTANCE: f32 = 0.005;
+
+/// How far the mouse must travel while pressed before a click becomes a drag, in pixels.
+const DRAG_THRESHOLD: f32 = 8.;
+
+/// How far a selected card is raised above its layout position.
+const SELECTED_RAISE: f32 = 0.5;
+
+/// How long a touch must be held still before the card is enlarged for inspection.
+const LONG_PRESS_MSEC: i64 = 500;
+
+/// How much a card is enlarged while a long-press holds it up for inspection.
+const INSPECT_SCALE: f32 = 2.;
+
+/// Where the card's art sits, between the title and the body text.
+const ART_OFFSET: (f32, f32, f32) = (0., 0.35, 0.01);
+const ART_HEIGHT: f32 = 0.8;
+
+const OUTLINE_NAME: &str = "SelectionOutline";
+const OUTLINE_SIZE: (f32, f32) = (1.7, 2.3);
+const OUTLINE_COLOR: (f32, f32, f32, f32) = (1., 0.85, 0.3, 0.9);
+
+/// Drawn just behind the card so only the rim shows.
+const OUTLINE_OFFSET: f32 = -0.01;
 
 pub(crate) const CARD_DRAGGED: SignalName = SignalName("card_dragged");
-const INPUT_EVENT: SignalName = SignalName("input_event");
+
+/// Emitted when a drag is abandoned with the cancel action.
+pub(crate) const CARD_DRAG_CANCELLED: SignalName = SignalName("card_drag_cancelled");
+
+/// Emitted when the card is pressed and released without being dragged.
+pub(crate) const CARD_CLICKED: SignalName = SignalName("card_clicked");
+
+/// Emitted when the player starts or stops inspecting the card:
+/// by hovering it, `input::INSPECT` over it, or with a long-press.
+pub(crate) const CARD_INSPECT_REQUESTED: SignalName = SignalName("card_inspect_requested");
 
 #[derive(NativeClass)]
 #[register_with(Self::register)]
@@ -24,6 +86,22 @@ pub struct CardInstance {
     state_is_following_mouse: bool,
     cost_label: NodeRef<TextBox, Spatial>,
     view: Option<UnitCardInstancePlayerView>,
+    layout: Option<CardLayout>,
+    is_moving_to_layout: bool,
+    is_selected: bool,
+    selection_outline: Option<Ref<MeshInstance>>,
+
+    /// Where the pointer was pressed on this card, until it is released or becomes a drag.
+    press_origin: Option<Vector2>,
+
+    /// When the pointer was pressed, in milliseconds since startup.
+    press_started_msec: i64,
+
+    /// The index and position of the touch holding this card, while there is one.
+    touch: Option<(i64, Vector2)>,
+
+    /// Whether a long-press is holding the card up for inspection.
+    is_inspecting: bool,
 }
 
 impl CardInstance {
@@ -34,6 +112,14 @@ impl CardInstance {
             state_is_following_mouse: false,
             cost_label: NodeRef::from_path(COST_LABEL),
             view: None,
+            layout: None,
+            is_moving_to_layout: false,
+            is_selected: false,
+            selection_outline: None,
+            press_origin: None,
+            press_started_msec: 0,
+            touch: None,
+            is_inspecting: false,
         }
     }
 
@@ -45,6 +131,7 @@ impl CardInstance {
         self.title = title.to_string();
     }
 
+    /// Sets the body text, as BBCode; see `card_text::format_card_text`.
     pub fn set_body(&mut self, body: impl ToString) {
         self.body = body.to_string();
     }
@@ -58,6 +145,144 @@ impl CardInstance {
         self.view.as_ref().unwrap()
     }
 
+    /// Sets the position this card should occupy in the hand and starts easing towards it.
+    pub(crate) fn move_to_layout(&mut self, layout: CardLayout) {
+        self.layout = Some(layout);
+        self.is_moving_to_layout = true;
+    }
+
+    /// Sends the card back to its layout position in the hand.
+    pub(crate) fn return_to_layout(&mut self) {
+        self.is_moving_to_layout = true;
+    }
+
+    /// Raises and outlines the card while it is selected for placing.
+    pub(crate) fn set_selected(&mut self, is_selected: bool) {
+        if self.is_selected == is_selected {
+            return;
+        }
+
+        self.is_selected = is_selected;
+        self.is_moving_to_layout = true;
+
+        if let Some(outline) = self.selection_outline {
+            unsafe { outline.assume_safe() }.set_visible(is_selected);
+        }
+    }
+
+    /// Eases the card one step towards its layout position.
+    fn layout_update(&mut self, owner: &Spatial, delta: f32) {
+        let layout = if let Some(layout) = self.layout {
+            layout
+        } else {
+            self.is_moving_to_layout = false;
+            return;
+        };
+
+        let mut target = layout.translation;
+        if self.is_selected {
+            target.y += SELECTED_RAISE;
+        }
+
+        let translation = owner.translation();
+        let rotation = owner.rotation_degrees();
+
+        if translation.distance_to(target) < LAYOUT_SNAP_DISTANCE {
+            owner.set_translation(target);
+            owner.set_rotation_degrees(layout.rotation_degrees);
+            self.is_moving_to_layout = false;
+            return;
+        }
+
+        let weight = (delta * LAYOUT_EASE_SPEED).min(1.);
+
+        owner.set_translation(translation.linear_interpolate(target, weight));
+        owner.set_rotation_degrees(rotation.linear_interpolate(layout.rotation_degrees, weight));
+    }
+
+    /// The screen position of whatever is holding the card: the touch if there is one,
+    /// otherwise the mouse.
+    fn pointer_position(&self, owner: &Spatial) -> Vector2 {
+        if let Some((_, touch_pos)) = self.touch {
+            return touch_pos;
+        }
+
+        let viewport = owner.get_viewport().unwrap();
+        unsafe { viewport.assume_safe() }.get_mouse_position()
+    }
+
+    fn pointer_pressed(&mut self, position: Vector2) {
+        self.press_origin = Some(position);
+        self.press_started_msec = OS::godot_singleton().get_ticks_msec();
+    }
+
+    /// Ends whatever the press started: a drag, an inspection or a click.
+    fn pointer_released(&mut self, owner: &Spatial, position: Vector2) {
+        if self.state_is_following_mouse {
+            self.follow_mouse_stop(owner, position);
+        } else if self.is_inspecting {
+            self.inspect_stop(owner);
+        } else if self.press_origin.take().is_some() {
+            info!("Emitting signal: CARD_CLICKED");
+            owner.emit_signal(CARD_CLICKED, &[owner.get_path().to_variant()]);
+        }
+    }
+
+    /// Turns a press into a drag once the pointer has moved far enough while held,
+    /// or, for a touch held still long enough, into an inspection.
+    fn press_update(&mut self, owner: &Spatial) {
+        let press_origin = if let Some(press_origin) = self.press_origin {
+            press_origin
+        } else {
+            return;
+        };
+
+        if self.touch.is_none() && !Input::godot_singleton().is_action_pressed(input::SELECT) {
+            self.press_origin = None;
+            return;
+        }
+
+        let pointer_pos = self.pointer_position(owner);
+
+        if pointer_pos.distance_to(press_origin) > DRAG_THRESHOLD {
+            self.press_origin = None;
+            self.follow_mouse_start(owner, pointer_pos);
+            return;
+        }
+
+        let held_msec = OS::godot_singleton().get_ticks_msec() - self.press_started_msec;
+
+        if self.touch.is_some() && held_msec >= LONG_PRESS_MSEC {
+            self.press_origin = None;
+            self.inspect_start(owner);
+        }
+    }
+
+    /// Enlarges the card in place so it can be read.
+    fn inspect_start(&mut self, owner: &Spatial) {
+        self.is_inspecting = true;
+        owner.set_scale(Vector3::new(INSPECT_SCALE, INSPECT_SCALE, 1.));
+        self.request_inspect(owner, true);
+    }
+
+    fn inspect_stop(&mut self, owner: &Spatial) {
+        self.is_inspecting = false;
+        owner.set_scale(Vector3::new(1., 1., 1.));
+        self.request_inspect(owner, false);
+    }
+
+    /// Asks for this card to be shown in, or removed from, the inspect overlay.
+    fn request_inspect(&self, owner: &Spatial, is_inspecting: bool) {
+        info!(
+            "Emitting signal: CARD_INSPECT_REQUESTED ({})",
+            if is_inspecting { "starting" } else { "ending" }
+        );
+        owner.emit_signal(
+            CARD_INSPECT_REQUESTED,
+            &[owner.get_path().to_variant(), is_inspecting.to_variant()],
+        );
+    }
+
     fn follow_mouse_start(&mut self, owner: &Spatial, mouse_pos: Vector2) {
         info!("Emitting signal: PLAYER_HAND_CARD_DRAGGED (starting)");
         owner.emit_signal(
@@ -71,6 +296,8 @@ impl CardInstance {
         self.state_is_following_mouse = true;
     }
 
+    /// Stops dragging and sends the card back to the hand.
+    /// If the drop lands on a slot, the card is summoned and freed before it gets there.
     fn follow_mouse_stop(&mut self, owner: &Spatial, mouse_pos: Vector2) {
         info!("Emitting signal: PLAYER_HAND_CARD_DRAGGED (ending)");
         owner.emit_signal(
@@ -82,6 +309,14 @@ impl CardInstance {
             ],
         );
         self.state_is_following_mouse = false;
+        self.return_to_layout();
+    }
+
+    fn follow_mouse_cancel(&mut self, owner: &Spatial) {
+        info!("Emitting signal: CARD_DRAG_CANCELLED");
+        self.state_is_following_mouse = false;
+        self.return_to_layout();
+        owner.emit_signal(CARD_DRAG_CANCELLED, &[owner.get_path().to_variant()]);
     }
 
     fn follow_mouse_update(&self, owner: &Spatial) {
@@ -93,7 +328,7 @@ impl CardInstance {
         let camera = root.get_camera().unwrap();
         let camera = unsafe { camera.assume_safe() };
 
-        let mouse_pos = root.get_mouse_position();
+        let mouse_pos = self.pointer_position(owner);
         let original_global_pos = owner.global_transform().origin;
         let card_z = original_global_pos.z as f64;
 
@@ -106,10 +341,8 @@ impl CardInstance {
         owner.set_global_transform(current);
     }
 
-    pub(crate) fn new_instance() -> Instance<CardInstance, Unique> {
-        let card_instance = util::load_scene(CARD_INSTANCE_SCENE).unwrap();
-        let card_instance = util::instance_scene::<Spatial>(&card_instance);
-        card_instance.cast_instance::<CardInstance>().unwrap()
+    pub(crate) fn new_instance() -> Result<Instance<CardInstance, Unique>, GuiError> {
+        scene_cache::instance_script(CARD_INSTANCE_SCENE)
     }
 }
 
@@ -117,51 +350,97 @@ impl CardInstance {
 impl CardInstance {
     #[export]
     fn _ready(&mut self, owner: TRef<Spatial>) {
-        self.cost_label.init_from_parent_ref(owner);
-
-        let body_text = owner
-            .get_node(BODY_TEXT_LABEL)
-            .expect("Did not find body text.");
-
-        let title_text = owner
-            .get_node(TITLE_TEXT_LABEL)
-            .expect("Did not find title text.");
-
-        unsafe {
-            body_text
-                .assume_safe_if_sane()
-                .expect("_body_text was not sane")
-                .set("text", &self.body);
-
-            title_text
-                .assume_safe_if_sane()
-                .expect("_title_text was not sane")
-                .set("text", &self.title);
-        }
-
-        let cost = self
-            .view
-            .as_ref()
-            .expect("The view should be set before _ready is invoked")
-            .definition()
-            .cost();
-
-        self.cost_label
-            .resolve_instance()
-            .map(|a, _| {
-                a.set_text(&cost.to_string());
-            })
-            .expect("Could not update cost label");
+        self.cost_label.try_init_from_parent_ref(owner).or_report();
+
+        if let Some(body_text) =
+            util::get_node::<RichTextLabel>(BODY_TEXT_LABEL, owner.upcast()).or_report()
+        {
+            body_text.set_use_bbcode(true);
+            body_text.set_bbcode(&self.body);
+        }
+
+        if let Some(title_text) =
+            util::get_node::<RichTextLabel>(TITLE_TEXT_LABEL, owner.upcast()).or_report()
+        {
+            title_text.set_text(&self.title);
+        }
+
+        if let Some(view) = &self.view {
+            let cost = card_text::format_cost(view.cost(), view.definition().cost());
 
-        let mouse_collider = owner.get_node("StaticBody").unwrap();
-        let mouse_collider = unsafe { mouse_collider.assume_safe_if_sane().unwrap() };
-        util::connect_signal(&*mouse_collider, INPUT_EVENT, owner, "input_event");
+            self.cost_label
+                .try_map(|a, _| a.set_bbcode(&cost))
+                .or_report();
+        } else {
+            warn!("Card {} was readied before its view was set", self.title);
+        }
+
+        card_art::add_art_sprite(
+            owner,
+            &self.title,
+            Vector3::new(ART_OFFSET.0, ART_OFFSET.1, ART_OFFSET.2),
+            ART_HEIGHT,
+        );
+
+        if let Some(collider) =
+            util::get_node::<StaticBody>(COLLIDER_PATH, owner.upcast()).or_report()
+        {
+            util::connect_signal(collider, INPUT_EVENT_SIGNAL, owner, "input_event");
+            util::connect_signal(collider, MOUSE_ENTERED_SIGNAL, owner, "on_mouse_entered");
+            util::connect_signal(collider, MOUSE_EXITED_SIGNAL, owner, "on_mouse_exited");
+        }
+
+        let (outline, material) = util::add_overlay_quad(
+            owner,
+            OUTLINE_NAME,
+            Vector2::new(OUTLINE_SIZE.0, OUTLINE_SIZE.1),
+            Vector3::new(0., 0., OUTLINE_OFFSET),
+        );
+        let (r, g, b, a) = OUTLINE_COLOR;
+        unsafe { material.assume_safe() }.set_albedo(Color::rgba(r, g, b, a));
+        unsafe { outline.assume_safe() }.set_visible(self.is_selected);
+        self.selection_outline = Some(outline);
     }
 
     #[export]
-    fn _physics_process(&mut self, owner: TRef<Spatial>, _delta: f32) {
+    fn _physics_process(&mut self, owner: TRef<Spatial>, delta: f32) {
         if self.state_is_following_mouse {
             self.follow_mouse_update(owner.as_ref());
+        } else if self.press_origin.is_some() {
+            self.press_update(owner.as_ref());
+        } else if self.is_moving_to_layout {
+            self.layout_update(owner.as_ref(), delta);
+        }
+    }
+
+    #[export]
+    fn _unhandled_input(&mut self, owner: TRef<Spatial>, event: Ref<InputEvent>) {
+        let event = unsafe { event.assume_safe() };
+
+        // Follow the touch holding this card even once it has slid off the card.
+        if let Some((touch_index, _)) = self.touch {
+            if let Some(drag) = event.cast::<InputEventScreenDrag>() {
+                if drag.index() == touch_index {
+                    self.touch = Some((touch_index, drag.position()));
+                }
+            } else if let Some(touch) = event.cast::<InputEventScreenTouch>() {
+                if touch.index() == touch_index && !touch.is_pressed() {
+                    self.pointer_released(&owner, touch.position());
+                    self.touch = None;
+                }
+            }
+        }
+
+        if !self.state_is_following_mouse {
+            return;
+        }
+
+        if event.is_action_pressed(input::CANCEL, false) {
+            self.follow_mouse_cancel(&owner);
+
+            if let Some(tree) = owner.get_tree() {
+                unsafe { tree.assume_safe() }.set_input_as_handled();
+            }
         }
     }
 
@@ -177,17 +456,60 @@ impl CardInstance {
         _click_normal: Variant,
         _shape_idx: Variant,
     ) {
-        if let Some(event) = mouse_event.try_to_object::<InputEventMouseButton>() {
+        if let Some(event) = mouse_event.try_to_object::<InputEventScreenTouch>() {
+            let touch = unsafe { event.assume_safe() };
+            let position = touch.position();
+
+            if touch.is_pressed() {
+                self.touch = Some((touch.index(), position));
+                self.pointer_pressed(position);
+            } else if self.touch.is_some() {
+                self.pointer_released(&owner, position);
+                self.touch = None;
+            }
+        } else if let Some(event) = mouse_event.try_to_object::<InputEventMouse>() {
             let click = unsafe { event.assume_safe() };
+            if input::is_emulated_from_touch(&click) {
+                return;
+            }
+
+            let is_inspect =
+                click.is_action_pressed(input::INSPECT, false) || input::is_inspect_click(&click);
+
+            if is_inspect && !self.state_is_following_mouse {
+                self.request_inspect(&owner, true);
+                return;
+            }
+
+            if !click.is_action(input::SELECT) {
+                return;
+            }
+
             let position = click.position();
             if click.is_pressed() {
-                self.follow_mouse_start(&owner, position);
+                self.pointer_pressed(position);
             } else {
-                self.follow_mouse_stop(&owner, position);
+                self.pointer_released(&owner, position);
             }
         }
     }
 
+    /// Shows the card in the inspect overlay while the mouse is over it.
+    /// Touches inspect with a long-press instead, and a dragged card is already in view.
+    #[export]
+    fn on_mouse_entered(&mut self, owner: TRef<Spatial>) {
+        if !self.state_is_following_mouse && self.touch.is_none() {
+            self.request_inspect(&owner, true);
+        }
+    }
+
+    #[export]
+    fn on_mouse_exited(&mut self, owner: TRef<Spatial>) {
+        if !self.is_inspecting {
+            self.request_inspect(&owner, false);
+        }
+    }
+
     fn register(builder: &ClassBuilder<Self>) {
         builder
             .add_property::<String>("title")
@@ -222,5 +544,43 @@ impl CardInstance {
                 },
             ],
         });
+
+        builder.add_signal(Signal {
+            name: CARD_DRAG_CANCELLED.as_ref(),
+            args: &[SignalArgument {
+                name: "path",
+                default: Variant::from_str("<empty_default>"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        builder.add_signal(Signal {
+            name: CARD_CLICKED.as_ref(),
+            args: &[SignalArgument {
+                name: "path",
+                default: Variant::from_str("<empty_default>"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        builder.add_signal(Signal {
+            name: CARD_INSPECT_REQUESTED.as_ref(),
+            args: &[
+                SignalArgument {
+                    name: "path",
+                    default: Variant::from_str("<empty_default>"),
+                    export_info: ExportInfo::new(VariantType::GodotString),
+                    usage: PropertyUsage::DEFAULT,
+                },
+                SignalArgument {
+                    name: "is_inspecting",
+                    default: Variant::from_bool(false),
+                    export_info: ExportInfo::new(VariantType::Bool),
+                    usage: PropertyUsage::DEFAULT,
+                },
+            ],
+        });
     }
 }
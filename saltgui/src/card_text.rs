@@ -0,0 +1,177 @@
+//! Turns card text and stats into BBCode for `RichTextLabel`s.
+
+use std::cmp::Ordering;
+
+/// Words and phrases in card text that carry rules meaning, drawn in bold.
+const KEYWORDS: &[&str] = &[
+    "Upon summon",
+    "Upon death",
+    "Start of turn",
+    "End of turn",
+    "Defender",
+    "Guard",
+    "Ranged",
+    "Rush",
+];
+
+const MANA_ICON: &str = "res://card/icons/mana.png";
+const ATTACK_ICON: &str = "res://card/icons/attack.png";
+const HEALTH_ICON: &str = "res://card/icons/health.png";
+
+/// Icons are drawn at the height of the surrounding text.
+const ICON_SIZE: u32 = 16;
+
+/// A stat raised above the card's definition.
+const BUFFED_COLOR: &str = "#5fd35f";
+
+/// A stat lowered below the card's definition.
+const REDUCED_COLOR: &str = "#e05050";
+
+/// Converts card text to BBCode: keywords are bolded, and `{mana}`, `{attack}` and `{health}`
+/// become icons. Anything that looks like BBCode in the text itself is escaped.
+pub(crate) fn format_card_text(text: &str) -> String {
+    let mut formatted = escape(text);
+
+    for keyword in KEYWORDS {
+        formatted = bold_word(&formatted, keyword);
+    }
+
+    formatted
+        .replace("{mana}", &icon(MANA_ICON))
+        .replace("{attack}", &icon(ATTACK_ICON))
+        .replace("{health}", &icon(HEALTH_ICON))
+}
+
+/// A card's cost with the mana icon, colored if it differs from the definition.
+pub(crate) fn format_cost(cost: i32, base_cost: i32) -> String {
+    // A cheaper card is an improvement, unlike a lower attack or health.
+    format!("{}{}", icon(MANA_ICON), stat(cost, base_cost, base_cost.cmp(&cost)))
+}
+
+/// A creature's attack and health with icons, colored where they differ from the definition.
+pub(crate) fn format_stats(
+    attack: i32,
+    health: i32,
+    base_attack: i32,
+    base_health: i32,
+) -> String {
+    format!(
+        "{}{} {}{}",
+        icon(ATTACK_ICON),
+        stat(attack, base_attack, attack.cmp(&base_attack)),
+        icon(HEALTH_ICON),
+        stat(health, base_health, health.cmp(&base_health)),
+    )
+}
+
+/// `value`, colored by whether the change from `base` is good (`Greater`) or bad (`Less`).
+fn stat(value: i32, base: i32, change: Ordering) -> String {
+    if value == base {
+        return value.to_string();
+    }
+
+    let color = match change {
+        Ordering::Greater => BUFFED_COLOR,
+        Ordering::Less | Ordering::Equal => REDUCED_COLOR,
+    };
+
+    format!("[color={}]{}[/color]", color, value)
+}
+
+fn icon(path: &str) -> String {
+    format!("[img={}]{}[/img]", ICON_SIZE, path)
+}
+
+/// Escapes `[` so text can't open BBCode tags of its own.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('[', "[lb]")
+}
+
+/// Bolds every whole-word occurrence of `word` in `text`.
+fn bold_word(text: &str, word: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find(word) {
+        let (before, after) = rest.split_at(index);
+        let after = &after[word.len()..];
+
+        let starts_word = !before.chars().last().map_or(false, char::is_alphanumeric);
+        let ends_word = !after.chars().next().map_or(false, char::is_alphanumeric);
+
+        result.push_str(before);
+        if starts_word && ends_word {
+            result.push_str("[b]");
+            result.push_str(word);
+            result.push_str("[/b]");
+        } else {
+            result.push_str(word);
+        }
+
+        rest = after;
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_neutralizes_tags() {
+        assert_eq!(escape("[b]bold[/b]"), "[lb]b]bold[lb]/b]");
+    }
+
+    #[test]
+    fn keywords_are_bolded_only_as_whole_words() {
+        assert_eq!(format_card_text("Rush. Rushing"), "[b]Rush[/b]. Rushing");
+        assert_eq!(
+            format_card_text("Upon death: Guard"),
+            "[b]Upon death[/b]: [b]Guard[/b]"
+        );
+        assert_eq!(format_card_text("Guardian"), "Guardian");
+    }
+
+    #[test]
+    fn card_text_bbcode_is_escaped_before_formatting() {
+        assert_eq!(format_card_text("[i]Guard"), "[lb]i][b]Guard[/b]");
+    }
+
+    #[test]
+    fn placeholders_become_icons() {
+        assert_eq!(
+            format_card_text("Gain 1 {mana}, +1 {attack} and +1 {health}"),
+            format!(
+                "Gain 1 {}, +1 {} and +1 {}",
+                icon(MANA_ICON),
+                icon(ATTACK_ICON),
+                icon(HEALTH_ICON)
+            )
+        );
+    }
+
+    #[test]
+    fn unchanged_stats_are_not_colored() {
+        assert_eq!(
+            format_stats(2, 3, 2, 3),
+            format!("{}2 {}3", icon(ATTACK_ICON), icon(HEALTH_ICON))
+        );
+        assert_eq!(format_cost(4, 4), format!("{}4", icon(MANA_ICON)));
+    }
+
+    #[test]
+    fn changed_stats_are_colored_by_direction() {
+        let stats = format_stats(3, 1, 2, 3);
+
+        assert!(stats.contains(&format!("[color={}]3[/color]", BUFFED_COLOR)));
+        assert!(stats.contains(&format!("[color={}]1[/color]", REDUCED_COLOR)));
+    }
+
+    #[test]
+    fn cheaper_cost_is_a_buff() {
+        assert!(format_cost(1, 3).ends_with(&format!("[color={}]1[/color]", BUFFED_COLOR)));
+        assert!(format_cost(5, 3).ends_with(&format!("[color={}]5[/color]", REDUCED_COLOR)));
+    }
+}
@@ -1,14 +1,31 @@
 use crate::{
-    board_slot::INPUT_EVENT_SIGNAL,
-    textbox::TextBox,
+    error::OrReport,
+    input,
+    scene_contract::{RequiredNode, SceneContract},
+    textbox::{self, TextBox},
     util::{self, NodeRef},
-    SignalName,
+    SignalName, INPUT_EVENT_SIGNAL,
 };
-use gdnative::{api::InputEventMouseButton, prelude::*};
+use gdnative::api::StaticBody;
+use gdnative::prelude::*;
 use log::info;
 
 pub(crate) const END_TURN_CLICKED_SIGNAL: SignalName = SignalName("end_turn_clicked");
 
+const TEXT_BOX_PATH: &str = "TextBox";
+const COLLIDER_PATH: &str = "StaticBody";
+
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "EndTurnButton",
+    scene: None,
+    nodes: &[
+        RequiredNode::new(TEXT_BOX_PATH, "Spatial")
+            .with_script("TextBox")
+            .containing(&textbox::SCENE_CONTRACT),
+        RequiredNode::new(COLLIDER_PATH, "StaticBody"),
+    ],
+};
+
 #[derive(NativeClass)]
 #[register_with(Self::register)]
 #[inherit(Spatial)]
@@ -19,17 +36,14 @@ pub struct EndTurnButton {
 impl EndTurnButton {
     fn new(_owner: &Spatial) -> Self {
         Self {
-            text_box: NodeRef::<TextBox, Spatial>::from_path("TextBox"),
+            text_box: NodeRef::<TextBox, Spatial>::from_path(TEXT_BOX_PATH),
         }
     }
 
     pub fn set_text(&self, text: &str) {
         self.text_box
-            .resolve_instance()
-            .map_mut(|t, _| {
-                t.set_text(text);
-            })
-            .expect("Could not set text on EndTurnButton textbox");
+            .try_map_mut(|t, _| t.set_text(text))
+            .or_report();
     }
 }
 
@@ -39,11 +53,13 @@ impl EndTurnButton {
     fn _ready(&mut self, owner: TRef<Spatial>) {
         info!("End turn button initialized.");
 
-        self.text_box.init_from_parent_ref(owner);
+        self.text_box.try_init_from_parent_ref(owner).or_report();
 
-        let mouse_collider = owner.get_node("StaticBody").unwrap();
-        let mouse_collider = unsafe { mouse_collider.assume_safe_if_sane().unwrap() };
-        util::connect_signal(&*mouse_collider, INPUT_EVENT_SIGNAL, owner, "input_event");
+        if let Some(collider) =
+            util::get_node::<StaticBody>(COLLIDER_PATH, owner.upcast()).or_report()
+        {
+            util::connect_signal(collider, INPUT_EVENT_SIGNAL, owner, "input_event");
+        }
     }
 
     #[export]
@@ -58,9 +74,9 @@ impl EndTurnButton {
         _click_normal: Variant,
         _shape_idx: Variant,
     ) {
-        if let Some(event) = mouse_event.try_to_object::<InputEventMouseButton>() {
-            let click = unsafe { event.assume_safe() };
-            if !click.is_pressed() {
+        if let Some(event) = mouse_event.try_to_object::<InputEvent>() {
+            let event = unsafe { event.assume_safe() };
+            if input::is_select_released(&event) {
                 owner.emit_signal(END_TURN_CLICKED_SIGNAL, &[]);
             }
         }
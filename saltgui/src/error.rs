@@ -0,0 +1,132 @@
+use crate::{board::BoardError, locale, toast};
+use log::{debug, error};
+use std::any::type_name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Something the GUI couldn't do, usually because the scene isn't laid out the way the code
+/// expects. These are reported rather than panicking, so one broken node doesn't end the game.
+#[derive(Debug, Error)]
+pub(crate) enum GuiError {
+    #[error("node ref {path} was used before it was initialized")]
+    NotInitialized { path: String },
+
+    #[error("no node at {path}; expected a {godot_class}")]
+    NodeMissing {
+        path: String,
+        godot_class: &'static str,
+    },
+
+    #[error("node at {path} is not a {godot_class}")]
+    WrongClass {
+        path: String,
+        godot_class: &'static str,
+    },
+
+    #[error("no node at {path}; expected a {godot_base} with the {rust_type} script")]
+    NodeNotFound {
+        path: String,
+        rust_type: &'static str,
+        godot_base: &'static str,
+    },
+
+    #[error("node at {path} is not a {godot_base}, as {rust_type} requires")]
+    WrongBase {
+        path: String,
+        rust_type: &'static str,
+        godot_base: &'static str,
+    },
+
+    #[error("node at {path} is a {godot_base} without the {rust_type} script")]
+    MissingScript {
+        path: String,
+        rust_type: &'static str,
+        godot_base: &'static str,
+    },
+
+    /// The script is already in use further up the call stack, e.g. by a signal's sender.
+    #[error("the {rust_type} script at {path} is already in use: {reason}")]
+    Borrowed {
+        path: String,
+        rust_type: &'static str,
+        reason: String,
+    },
+
+    #[error("could not load the scene {path}")]
+    SceneNotLoaded { path: String },
+
+    #[error("the root of the scene {path} is not a {godot_base}")]
+    WrongSceneRoot {
+        path: String,
+        godot_base: &'static str,
+    },
+
+    #[error(transparent)]
+    Board(#[from] BoardError),
+}
+
+impl GuiError {
+    /// The error for a `T` script that couldn't be borrowed, at `path`.
+    pub(crate) fn borrowed<T>(path: impl Into<String>, reason: impl Debug) -> Self {
+        GuiError::Borrowed {
+            path: path.into(),
+            rust_type: short_type_name::<T>(),
+            reason: format!("{:?}", reason),
+        }
+    }
+}
+
+/// The name of `T` without its module path, for error messages.
+pub(crate) fn short_type_name<T>() -> &'static str {
+    let name = type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// How long an error stays quiet after it's reported, before it's reported again.
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+thread_local! {
+    /// When each recent message was last reported, so an error hit every frame isn't.
+    static REPORTED: RefCell<HashMap<String, Instant>> = RefCell::new(HashMap::new());
+}
+
+/// Logs an error and shows it in a toast. Repeats within `REPORT_INTERVAL` are only logged at
+/// debug level, so a node that's looked up every frame doesn't flood the log or the screen.
+pub(crate) fn report(error: &GuiError) {
+    let message = error.to_string();
+    let now = Instant::now();
+
+    let is_due = REPORTED.with(|reported| {
+        let mut reported = reported.borrow_mut();
+        reported.retain(|_, last| now.duration_since(*last) < REPORT_INTERVAL);
+
+        if reported.contains_key(&message) {
+            false
+        } else {
+            reported.insert(message.clone(), now);
+            true
+        }
+    });
+
+    if is_due {
+        error!("{}", message);
+        toast::show(locale::tr_with("error.toast", &[("error", &message)]));
+    } else {
+        debug!("{}", message);
+    }
+}
+
+/// Reports a failed `Result` instead of panicking on it.
+pub(crate) trait OrReport<T> {
+    /// The value, or `None` after reporting the error.
+    fn or_report(self) -> Option<T>;
+}
+
+impl<T> OrReport<T> for Result<T, GuiError> {
+    fn or_report(self) -> Option<T> {
+        self.map_err(|e| report(&e)).ok()
+    }
+}
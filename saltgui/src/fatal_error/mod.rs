@@ -0,0 +1,111 @@
+use crate::{locale, util, PRESSED_SIGNAL};
+use gdnative::api::{
+    Button, ColorRect, GlobalConstants, Label, RichTextLabel, ScrollContainer, VBoxContainer,
+};
+use gdnative::prelude::*;
+use godot_log::PanicReport;
+use log::info;
+
+/// How much the game behind the report is dimmed, so it's clear the game has stopped.
+const BACKDROP_ALPHA: f32 = 0.85;
+
+/// Space between the screen's edges and the report.
+const MARGIN: f64 = 48.;
+
+/// A full-screen report of a panic, with its details and a button to quit.
+/// Created by `World` when a panic is reported, rather than placed in the scene.
+#[derive(NativeClass)]
+#[inherit(Control)]
+pub struct FatalErrorScreen;
+
+impl FatalErrorScreen {
+    fn new(_owner: &Control) -> Self {
+        Self
+    }
+
+    /// Adds a screen showing `report` to `parent`, covering everything else.
+    pub(crate) fn show(parent: TRef<Node>, report: &PanicReport) {
+        let screen = Instance::<Self, Unique>::new();
+        let owner = screen.base();
+
+        owner.set_name("FatalErrorScreen");
+        owner.set_anchors_preset(Control::PRESET_WIDE, false);
+        // Swallow every click, so nothing behind the screen can be used.
+        owner.set_mouse_filter(Control::MOUSE_FILTER_STOP);
+
+        let backdrop = ColorRect::new();
+        backdrop.set_frame_color(Color::rgba(0., 0., 0., BACKDROP_ALPHA));
+        backdrop.set_anchors_preset(Control::PRESET_WIDE, false);
+        owner.add_child(backdrop, false);
+
+        let rows = VBoxContainer::new();
+        rows.set_anchors_preset(Control::PRESET_WIDE, false);
+        rows.set_margin(GlobalConstants::MARGIN_LEFT, MARGIN);
+        rows.set_margin(GlobalConstants::MARGIN_TOP, MARGIN);
+        rows.set_margin(GlobalConstants::MARGIN_RIGHT, -MARGIN);
+        rows.set_margin(GlobalConstants::MARGIN_BOTTOM, -MARGIN);
+
+        let title = Label::new();
+        title.set_text(locale::tr("fatal.title"));
+        rows.add_child(title, false);
+
+        let message = Label::new();
+        message.set_autowrap(true);
+        message.set_text(&report.message);
+        rows.add_child(message, false);
+
+        let location = Label::new();
+        location.set_text(locale::tr_with(
+            "fatal.location",
+            &[
+                ("location", &report.location.as_deref().unwrap_or("?")),
+                ("thread", &report.thread),
+            ],
+        ));
+        rows.add_child(location, false);
+
+        let details_title = Label::new();
+        details_title.set_text(locale::tr("fatal.details"));
+        rows.add_child(details_title, false);
+
+        // The backtrace is long, so it scrolls and can be selected to copy into a bug report.
+        let details = ScrollContainer::new();
+        details.set_v_size_flags(Control::SIZE_EXPAND_FILL);
+        let backtrace = RichTextLabel::new();
+        backtrace.set_selection_enabled(true);
+        backtrace.set_fit_content_height(true);
+        backtrace.set_h_size_flags(Control::SIZE_EXPAND_FILL);
+        backtrace.set_text(&report.backtrace);
+        details.add_child(backtrace, false);
+        rows.add_child(details, false);
+
+        let quit = Button::new().into_shared();
+        unsafe { quit.assume_safe() }.set_text(locale::tr("fatal.quit"));
+        rows.add_child(quit, false);
+
+        owner.add_child(rows, false);
+
+        let screen = screen.into_shared();
+        let owner = unsafe { screen.base().assume_safe() };
+
+        parent.add_child(owner, false);
+        util::connect_signal(
+            unsafe { quit.assume_safe() },
+            PRESSED_SIGNAL,
+            owner,
+            "on_quit_pressed",
+        );
+    }
+}
+
+#[methods]
+impl FatalErrorScreen {
+    #[export]
+    fn on_quit_pressed(&self, owner: TRef<Control>) {
+        info!("Quitting after a fatal error.");
+
+        if let Some(tree) = owner.get_tree() {
+            unsafe { tree.assume_safe() }.quit(0);
+        }
+    }
+}
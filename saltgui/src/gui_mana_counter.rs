@@ -1,15 +1,29 @@
-use crate::util::NodeRef;
+use crate::{
+    error::OrReport,
+    locale,
+    scene_contract::{RequiredNode, SceneContract},
+    util::NodeRef,
+};
 use gdnative::{api::RichTextLabel, prelude::*};
 use log::{info, warn};
 
 const LABEL_PATH: &str = "RichTextLabel";
 
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "ManaCounter",
+    scene: None,
+    nodes: &[RequiredNode::new(LABEL_PATH, "RichTextLabel")],
+};
+
 #[derive(NativeClass, Debug)]
 #[register_with(Self::register)]
 #[inherit(Control)]
 pub struct ManaCounter {
     textbox: NodeRef<RichTextLabel, Control>,
     is_ready: bool,
+
+    /// The last mana shown, as (available, limit), so it can be redrawn in another language.
+    display: Option<(usize, usize)>,
 }
 
 impl ManaCounter {
@@ -17,6 +31,7 @@ impl ManaCounter {
         Self {
             textbox: NodeRef::from_path(LABEL_PATH),
             is_ready: false,
+            display: None,
         }
     }
 }
@@ -25,29 +40,48 @@ impl ManaCounter {
 impl ManaCounter {
     #[export]
     fn _ready(&mut self, owner: TRef<Control>) {
-        self.textbox.init_from_parent(owner);
-        self.is_ready = true;
+        self.is_ready = self
+            .textbox
+            .try_init_from_parent(owner)
+            .or_report()
+            .is_some();
+    }
+
+    pub fn set_display(&mut self, available: usize, limit: usize) {
+        self.display = Some((available, limit));
+        self.refresh_text();
     }
 
-    pub fn set_display(&self, available: usize, limit: usize) {
-        self.set_text(&format!("Mana: {}/{}", available, limit));
+    /// Redraws the last mana shown in the current language.
+    pub fn refresh_text(&self) {
+        if let Some((available, limit)) = self.display {
+            self.set_text(&locale::tr_with(
+                "mana.display",
+                &[("available", &available), ("limit", &limit)],
+            ));
+        }
     }
 
     fn set_text(&self, text: &str) {
         if self.is_ready {
             info!("Setting textbox text to: {}", text);
-            self.textbox.resolve_ref().set_text(text);
+            self.textbox
+                .try_resolve()
+                .map(|t| t.set_text(text))
+                .or_report();
         } else {
             warn!("set_text invoked when TextBox is not yet ready");
         }
     }
 
     pub fn get_text(&self) -> GodotString {
-        if self.is_ready {
-            self.textbox.resolve_ref().text()
+        let text = if self.is_ready {
+            self.textbox.try_resolve().map(|t| t.text()).or_report()
         } else {
-            "<TextBox not yet ready>".into()
-        }
+            None
+        };
+
+        text.unwrap_or_else(|| "<TextBox not yet ready>".into())
     }
 
     fn register(builder: &ClassBuilder<Self>) {
@@ -0,0 +1,133 @@
+use gdnative::prelude::*;
+
+/// The widest spacing allowed between two neighbouring cards.
+const MAX_CARD_SPACING: f32 = 1.75;
+
+/// The total width the hand may occupy before cards start overlapping more tightly.
+const MAX_HAND_WIDTH: f32 = 8.;
+
+/// The tilt between two neighbouring cards, in degrees.
+const MAX_FAN_ANGLE_STEP: f32 = 4.;
+
+/// The tilt of the outermost cards never exceeds this, in degrees.
+const MAX_FAN_ANGLE: f32 = 18.;
+
+/// How far the outermost cards drop below the centre of the arc.
+const FAN_ARC_DEPTH: f32 = 0.5;
+
+/// Cards further right sit slightly closer to the camera so they never z-fight.
+const CARD_DEPTH_STEP: f32 = 0.01;
+
+/// Where a single card sits within the hand, relative to the hand's origin.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct CardLayout {
+    pub translation: Vector3,
+    pub rotation_degrees: Vector3,
+}
+
+/// Lays out `card_count` cards in a fan centered on the hand's origin.
+/// The spacing and tilt shrink as the hand grows so it never exceeds `MAX_HAND_WIDTH`.
+pub(crate) fn fan_layout(card_count: usize) -> Vec<CardLayout> {
+    if card_count == 0 {
+        return vec![];
+    }
+
+    let gaps = (card_count - 1) as f32;
+
+    let spacing = if gaps > 0. {
+        MAX_CARD_SPACING.min(MAX_HAND_WIDTH / gaps)
+    } else {
+        0.
+    };
+
+    let angle_step = if gaps > 0. {
+        MAX_FAN_ANGLE_STEP.min(2. * MAX_FAN_ANGLE / gaps)
+    } else {
+        0.
+    };
+
+    let center = gaps / 2.;
+
+    (0..card_count)
+        .map(|i| {
+            let from_center = i as f32 - center;
+
+            // -1 for the leftmost card, 1 for the rightmost.
+            let normalized = if center > 0. {
+                from_center / center
+            } else {
+                0.
+            };
+
+            CardLayout {
+                translation: Vector3::new(
+                    from_center * spacing,
+                    -FAN_ARC_DEPTH * normalized * normalized,
+                    i as f32 * CARD_DEPTH_STEP,
+                ),
+                rotation_degrees: Vector3::new(0., 0., -from_center * angle_step),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    #[test]
+    fn an_empty_hand_has_no_layout() {
+        assert!(fan_layout(0).is_empty());
+    }
+
+    #[test]
+    fn a_single_card_is_centered_and_upright() {
+        let layout = fan_layout(1);
+
+        assert_eq!(layout.len(), 1);
+        assert_eq!(layout[0].translation, Vector3::new(0., 0., 0.));
+        assert_eq!(layout[0].rotation_degrees, Vector3::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn cards_are_mirrored_around_the_center() {
+        for count in 2..=12 {
+            let layout = fan_layout(count);
+
+            for (left, right) in layout.iter().zip(layout.iter().rev()) {
+                assert!((left.translation.x + right.translation.x).abs() < EPSILON);
+                assert!((left.translation.y - right.translation.y).abs() < EPSILON);
+                assert!((left.rotation_degrees.z + right.rotation_degrees.z).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn cards_further_right_are_closer_to_the_camera() {
+        let layout = fan_layout(5);
+
+        assert!(layout
+            .windows(2)
+            .all(|pair| pair[1].translation.z > pair[0].translation.z));
+    }
+
+    #[test]
+    fn the_hand_compresses_to_stay_within_bounds() {
+        let spread = |count| {
+            let layout = fan_layout(count);
+            layout[count - 1].translation.x - layout[0].translation.x
+        };
+
+        // Small hands use the widest spacing.
+        assert!((spread(3) - 2. * MAX_CARD_SPACING).abs() < EPSILON);
+
+        for count in 2..=30 {
+            assert!(spread(count) <= MAX_HAND_WIDTH + EPSILON);
+
+            let outermost = fan_layout(count)[0].rotation_degrees.z;
+            assert!(outermost.abs() <= MAX_FAN_ANGLE + EPSILON);
+        }
+    }
+}
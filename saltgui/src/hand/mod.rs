@@ -1,64 +1,157 @@
+mod layout;
+
+pub(crate) use layout::CardLayout;
+
 use crate::{
-    card_instance::{CardInstance, CARD_DRAGGED},
-    util, SignalName,
+    card_instance::{
+        CardInstance, CARD_CLICKED, CARD_DRAGGED, CARD_DRAG_CANCELLED, CARD_INSPECT_REQUESTED,
+        CARD_INSTANCE_SCENE,
+    },
+    card_text,
+    error::{GuiError, OrReport},
+    util::{self, NodeRef},
+    SignalName,
 };
 use gdnative::prelude::*;
-use log::info;
+use log::{info, warn};
 use salt_engine::{cards::UnitCardDefinitionView, game_state::UnitCardInstancePlayerView};
 
-const OFFSET_DIST_MULTIPLIER: f32 = 1.75;
 pub(crate) const PLAYER_HAND_CARD_ADDED_SIGNAL: SignalName =
     SignalName("card_added_to_player_hand");
 
 pub(crate) const PLAYER_HAND_CARD_DRAGGED: SignalName = SignalName("player_hand_card_dragged");
+pub(crate) const PLAYER_HAND_CARD_DRAG_CANCELLED: SignalName =
+    SignalName("player_hand_card_drag_cancelled");
+pub(crate) const PLAYER_HAND_CARD_CLICKED: SignalName = SignalName("player_hand_card_clicked");
+pub(crate) const PLAYER_HAND_CARD_INSPECT_REQUESTED: SignalName =
+    SignalName("player_hand_card_inspect_requested");
 
 #[derive(NativeClass)]
 #[register_with(Self::register)]
 #[inherit(Spatial)]
 pub struct Hand {
-    hand_len: i32,
+    /// The cards in the hand, ordered left to right.
+    cards: Vec<NodeRef<CardInstance, Spatial>>,
 }
 
 impl Hand {
     fn new(_owner: &Spatial) -> Self {
-        Self { hand_len: 0 }
+        Self { cards: vec![] }
+    }
+
+    pub fn hand_len(&self) -> usize {
+        self.cards.len()
     }
 
     pub fn add_card(&mut self, card: &UnitCardInstancePlayerView, owner: TRef<Spatial>) {
         info!("Hand is receiving a card: {} {:?}", card.definition().title(), card.id());
 
-        let card_instance = CardInstance::new_instance();
-
-        // let offset = hand.map(|n, _| n.hand_len).unwrap() as f32 * OFFSET_DIST_MULTIPLIER;
-        let offset = self.hand_len as f32 * OFFSET_DIST_MULTIPLIER;
+        let card_instance = if let Some(card_instance) = CardInstance::new_instance().or_report() {
+            card_instance
+        } else {
+            return;
+        };
 
-        card_instance
+        let initialized = card_instance
             .map_mut(|c, n| {
                 let def = card.definition();
                 c.set_title(def.title());
-                c.set_body(def.text());
+                c.set_body(format!(
+                    "{}\n{}",
+                    card_text::format_card_text(def.text()),
+                    card_text::format_stats(
+                        card.attack(),
+                        card.health(),
+                        def.attack(),
+                        def.health(),
+                    ),
+                ));
 
                 c.set_view(card.clone());
 
-                n.translate(Vector3::new(offset, 0., 0.));
-
                 util::connect_signal(n, CARD_DRAGGED, owner, "on_card_dragged");
+                util::connect_signal(n, CARD_DRAG_CANCELLED, owner, "on_card_drag_cancelled");
+                util::connect_signal(n, CARD_CLICKED, owner, "on_card_clicked");
+                util::connect_signal(n, CARD_INSPECT_REQUESTED, owner, "on_card_inspect_requested");
             })
-            .unwrap();
+            .map_err(|e| GuiError::borrowed::<CardInstance>(CARD_INSTANCE_SCENE, e));
 
-        self.hand_len += 1;
+        if initialized.or_report().is_none() {
+            card_instance.into_base().free();
+            return;
+        }
 
         let card_instance = card_instance.into_base();
         let card_instance = card_instance.into_shared();
         owner.add_child(card_instance, false);
 
-        let card_instance = unsafe { card_instance.assume_safe() };
-        let card_path = card_instance.get_path();
+        let card_node = unsafe { card_instance.assume_safe() };
+        let card_path = card_node.get_path();
+
+        self.cards.push(NodeRef::from_existing(
+            card_path.to_string(),
+            card_instance.upcast::<Node>(),
+        ));
+        self.relayout();
 
         owner.emit_signal(PLAYER_HAND_CARD_ADDED_SIGNAL, &[card_path.to_variant()]);
 
         info!("Added card {:?} to PlayerHand.", card_path);
     }
+
+    /// Removes the card at the given path from the hand and frees it.
+    /// The remaining cards slide over to close the gap.
+    pub fn remove_card(&mut self, card_path: impl AsRef<str>, owner: TRef<Spatial>) {
+        let card_path = card_path.as_ref();
+
+        let index = if let Some(index) = self.index_of(card_path) {
+            index
+        } else {
+            warn!("Tried to remove card {} which is not in the hand.", card_path);
+            return;
+        };
+
+        let card = self.cards.remove(index);
+
+        if let Some(card) = card.try_resolve_instance().or_report() {
+            let card = card.base();
+            owner.remove_child(card);
+            card.queue_free();
+        }
+
+        self.relayout();
+
+        info!("Removed card {} from PlayerHand.", card_path);
+    }
+
+    /// The path of the card at the given index, counting from the left.
+    pub fn card_path(&self, index: usize) -> Option<String> {
+        self.cards.get(index).map(|c| c.path().to_string())
+    }
+
+    /// The index of the card at the given path, counting from the left.
+    pub fn index_of(&self, card_path: &str) -> Option<usize> {
+        self.cards.iter().position(|c| c.path() == card_path)
+    }
+
+    /// Raises and outlines the card at the given path, lowering every other card.
+    pub fn set_selected_card(&self, selected_path: Option<&str>) {
+        for card in &self.cards {
+            let is_selected = selected_path == Some(card.path());
+            card.try_map_mut(|c, _| c.set_selected(is_selected))
+                .or_report();
+        }
+    }
+
+    /// Recomputes the fan layout for every card and sends each one to its new position.
+    fn relayout(&self) {
+        let layouts = layout::fan_layout(self.cards.len());
+
+        for (card, layout) in self.cards.iter().zip(layouts) {
+            card.try_map_mut(|c, _| c.move_to_layout(layout))
+                .or_report();
+        }
+    }
 }
 
 #[methods]
@@ -71,8 +164,7 @@ impl Hand {
     fn register(builder: &ClassBuilder<Self>) {
         builder
             .add_property::<i32>("hand_len")
-            .with_getter(|s: &Self, _| s.hand_len)
-            .with_setter(|s, _, value| s.hand_len = value)
+            .with_getter(|s: &Self, _| s.hand_len() as i32)
             .done();
 
         builder.add_signal(Signal {
@@ -102,6 +194,44 @@ impl Hand {
                 },
             ],
         });
+
+        builder.add_signal(Signal {
+            name: PLAYER_HAND_CARD_DRAG_CANCELLED.as_ref(),
+            args: &[SignalArgument {
+                name: "path",
+                default: Variant::from_str("<empty_default>"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        builder.add_signal(Signal {
+            name: PLAYER_HAND_CARD_CLICKED.as_ref(),
+            args: &[SignalArgument {
+                name: "path",
+                default: Variant::from_str("<empty_default>"),
+                export_info: ExportInfo::new(VariantType::GodotString),
+                usage: PropertyUsage::DEFAULT,
+            }],
+        });
+
+        builder.add_signal(Signal {
+            name: PLAYER_HAND_CARD_INSPECT_REQUESTED.as_ref(),
+            args: &[
+                SignalArgument {
+                    name: "path",
+                    default: Variant::from_str("<empty_default>"),
+                    export_info: ExportInfo::new(VariantType::GodotString),
+                    usage: PropertyUsage::DEFAULT,
+                },
+                SignalArgument {
+                    name: "is_inspecting",
+                    default: Variant::from_bool(false),
+                    export_info: ExportInfo::new(VariantType::Bool),
+                    usage: PropertyUsage::DEFAULT,
+                },
+            ],
+        });
     }
 
     #[export]
@@ -123,4 +253,34 @@ impl Hand {
             &[dragged_card_path, is_ended, mouse_pos_2d],
         );
     }
+
+    #[export]
+    fn on_card_drag_cancelled(&self, owner: TRef<Spatial>, cancelled_card_path: Variant) {
+        info!("Hand saw card drag cancelled signal: {:?}", cancelled_card_path);
+
+        owner.emit_signal(PLAYER_HAND_CARD_DRAG_CANCELLED, &[cancelled_card_path]);
+    }
+
+    #[export]
+    fn on_card_clicked(&self, owner: TRef<Spatial>, clicked_card_path: Variant) {
+        info!("Hand saw card clicked signal: {:?}", clicked_card_path);
+
+        owner.emit_signal(PLAYER_HAND_CARD_CLICKED, &[clicked_card_path]);
+    }
+
+    #[export]
+    fn on_card_inspect_requested(
+        &self,
+        owner: TRef<Spatial>,
+        card_path: Variant,
+        is_inspecting: Variant,
+    ) {
+        info!(
+            "Hand saw card inspect signal: {:?} is inspecting: {}",
+            card_path,
+            is_inspecting.to_bool()
+        );
+
+        owner.emit_signal(PLAYER_HAND_CARD_INSPECT_REQUESTED, &[card_path, is_inspecting]);
+    }
 }
@@ -0,0 +1,332 @@
+use super::{ActionDefault, DEFAULT_ACTIONS};
+use crate::locale;
+use gdnative::api::{
+    ConfigFile, GlobalConstants, InputEventJoypadButton, InputEventJoypadMotion, InputEventKey,
+    InputEventMouseButton, InputMap, OS,
+};
+use gdnative::prelude::*;
+use log::{info, warn};
+
+/// Where the player's bindings are saved between sessions.
+const BINDINGS_PATH: &str = "user://input_bindings.cfg";
+const BINDINGS_SECTION: &str = "bindings";
+
+/// A joystick axis must move at least this far to be bound.
+const JOY_AXIS_BIND_THRESHOLD: f64 = 0.5;
+
+/// A single input that can trigger an action.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Binding {
+    /// A keyboard key, by scancode.
+    Key(i64),
+
+    /// A mouse button, by button index.
+    MouseButton(i64),
+
+    /// A gamepad button, by button index.
+    JoyButton(i64),
+
+    /// A gamepad axis, and the direction (-1 or 1) it must be pushed.
+    JoyAxis(i64, i64),
+}
+
+/// The kind of device a binding comes from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Device {
+    Keyboard,
+    Mouse,
+    Joypad,
+}
+
+impl Binding {
+    /// The binding for an input event, if it is an input that can be bound.
+    pub fn from_event(event: TRef<InputEvent>) -> Option<Self> {
+        if let Some(key) = event.cast::<InputEventKey>() {
+            Some(Binding::Key(key.scancode()))
+        } else if let Some(button) = event.cast::<InputEventMouseButton>() {
+            Some(Binding::MouseButton(button.button_index()))
+        } else if let Some(button) = event.cast::<InputEventJoypadButton>() {
+            Some(Binding::JoyButton(button.button_index()))
+        } else if let Some(motion) = event.cast::<InputEventJoypadMotion>() {
+            let value = motion.axis_value();
+
+            if value.abs() < JOY_AXIS_BIND_THRESHOLD {
+                None
+            } else {
+                let direction = if value < 0. { -1 } else { 1 };
+                Some(Binding::JoyAxis(motion.axis(), direction))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// An input event Godot will match against this binding.
+    pub fn to_event(self) -> Ref<InputEvent, Unique> {
+        match self {
+            Binding::Key(scancode) => {
+                let key = InputEventKey::new();
+                key.set_scancode(scancode);
+                key.upcast()
+            }
+            Binding::MouseButton(button_index) => {
+                let button = InputEventMouseButton::new();
+                button.set_button_index(button_index);
+                button.upcast()
+            }
+            Binding::JoyButton(button_index) => {
+                let button = InputEventJoypadButton::new();
+                button.set_button_index(button_index);
+                button.upcast()
+            }
+            Binding::JoyAxis(axis, direction) => {
+                let motion = InputEventJoypadMotion::new();
+                motion.set_axis(axis);
+                motion.set_axis_value(if direction < 0 { -1. } else { 1. });
+                motion.upcast()
+            }
+        }
+    }
+
+    /// The kind of device this comes from.
+    pub fn device(self) -> Device {
+        match self {
+            Binding::Key(_) => Device::Keyboard,
+            Binding::MouseButton(_) => Device::Mouse,
+            Binding::JoyButton(_) | Binding::JoyAxis(_, _) => Device::Joypad,
+        }
+    }
+
+    /// A short, human-readable name for the binding, e.g. "Escape" or "Mouse Right".
+    pub fn describe(self) -> String {
+        match self {
+            Binding::Key(scancode) => OS::godot_singleton().get_scancode_string(scancode).to_string(),
+            Binding::MouseButton(GlobalConstants::BUTTON_LEFT) => locale::tr("binding.mouse_left"),
+            Binding::MouseButton(GlobalConstants::BUTTON_RIGHT) => {
+                locale::tr("binding.mouse_right")
+            }
+            Binding::MouseButton(GlobalConstants::BUTTON_MIDDLE) => {
+                locale::tr("binding.mouse_middle")
+            }
+            Binding::MouseButton(button_index) => {
+                locale::tr_with("binding.mouse_button", &[("button", &button_index)])
+            }
+            Binding::JoyButton(button_index) => Input::godot_singleton()
+                .get_joy_button_string(button_index)
+                .to_string(),
+            Binding::JoyAxis(axis, direction) => format!(
+                "{} {}",
+                Input::godot_singleton().get_joy_axis_string(axis),
+                if direction < 0 { "-" } else { "+" }
+            ),
+        }
+    }
+
+    /// Encodes the binding for the config file, e.g. `key:16777217`.
+    fn encode(self) -> String {
+        match self {
+            Binding::Key(scancode) => format!("key:{}", scancode),
+            Binding::MouseButton(button_index) => format!("mouse:{}", button_index),
+            Binding::JoyButton(button_index) => format!("joy_button:{}", button_index),
+            Binding::JoyAxis(axis, direction) => format!("joy_axis:{}:{}", axis, direction),
+        }
+    }
+
+    /// The inverse of `encode`.
+    fn decode(encoded: &str) -> Option<Self> {
+        let mut parts = encoded.split(':');
+        let kind = parts.next()?;
+        let first = parts.next()?.parse().ok()?;
+
+        let binding = match kind {
+            "key" => Binding::Key(first),
+            "mouse" => Binding::MouseButton(first),
+            "joy_button" => Binding::JoyButton(first),
+            "joy_axis" => Binding::JoyAxis(first, parts.next()?.parse().ok()?),
+            _ => return None,
+        };
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(binding)
+    }
+}
+
+/// Replaces every binding of an action in Godot's `InputMap`.
+pub(crate) fn apply(action: &str, bindings: &[Binding]) {
+    let input_map = InputMap::godot_singleton();
+    input_map.action_erase_events(action);
+
+    for binding in bindings {
+        input_map.action_add_event(action, binding.to_event().into_shared());
+    }
+}
+
+/// The bindings currently mapped to an action.
+pub(crate) fn current(action: &str) -> Vec<Binding> {
+    InputMap::godot_singleton()
+        .get_action_list(action)
+        .iter()
+        .filter_map(|event| event.try_to_object::<InputEvent>())
+        .filter_map(|event| Binding::from_event(unsafe { event.assume_safe() }))
+        .collect()
+}
+
+/// Binds `binding` to an action, replacing the action's other bindings from the same kind of device.
+/// So rebinding with a key keeps the mouse and gamepad bindings.
+pub(crate) fn rebind(action: &str, binding: Binding) {
+    let mut bindings: Vec<Binding> = current(action)
+        .into_iter()
+        .filter(|b| b.device() != binding.device())
+        .collect();
+
+    bindings.insert(0, binding);
+
+    info!("Rebinding {} to {}", action, binding.describe());
+
+    apply(action, &bindings);
+    save();
+}
+
+/// Restores every gameplay action to its default bindings and saves.
+pub(crate) fn reset_to_defaults() {
+    for ActionDefault { name, bindings, .. } in DEFAULT_ACTIONS {
+        apply(name, bindings);
+    }
+
+    info!("Input bindings reset to defaults.");
+    save();
+}
+
+/// Writes every gameplay action's bindings to the player's settings.
+pub(crate) fn save() {
+    let config = ConfigFile::new();
+
+    for action in DEFAULT_ACTIONS {
+        let encoded = VariantArray::new();
+
+        for binding in current(action.name) {
+            encoded.push(GodotString::from(binding.encode()));
+        }
+
+        config.set_value(BINDINGS_SECTION, action.name, encoded.into_shared());
+    }
+
+    if let Err(e) = config.save(BINDINGS_PATH) {
+        warn!("Could not save input bindings to {}: {:?}", BINDINGS_PATH, e);
+    }
+}
+
+/// Applies the bindings saved in the player's settings, if there are any.
+/// Actions missing from the file keep their current bindings.
+pub(crate) fn load_saved() {
+    let config = ConfigFile::new();
+
+    if config.load(BINDINGS_PATH).is_err() {
+        info!("No saved input bindings at {}; using defaults.", BINDINGS_PATH);
+        return;
+    }
+
+    for action in DEFAULT_ACTIONS {
+        if !config.has_section_key(BINDINGS_SECTION, action.name) {
+            continue;
+        }
+
+        let encoded = config
+            .get_value(BINDINGS_SECTION, action.name, Variant::new())
+            .try_to_array();
+
+        let encoded = if let Some(encoded) = encoded {
+            encoded
+        } else {
+            warn!("Saved bindings for {} are not a list; ignoring them.", action.name);
+            continue;
+        };
+
+        let bindings: Vec<Binding> = encoded
+            .iter()
+            .filter_map(|b| {
+                let b = b.try_to_string().unwrap_or_default();
+                let decoded = Binding::decode(&b);
+
+                if decoded.is_none() {
+                    warn!("Ignoring unreadable binding {:?} for {}", b, action.name);
+                }
+
+                decoded
+            })
+            .collect();
+
+        apply(action.name, &bindings);
+    }
+
+    info!("Loaded input bindings from {}", BINDINGS_PATH);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_of_binding_round_trips() {
+        let bindings = [
+            Binding::Key(GlobalConstants::KEY_ESCAPE),
+            Binding::MouseButton(GlobalConstants::BUTTON_RIGHT),
+            Binding::JoyButton(GlobalConstants::JOY_XBOX_A),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_0, -1),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_1, 1),
+        ];
+
+        for &binding in &bindings {
+            assert_eq!(Binding::decode(&binding.encode()), Some(binding));
+        }
+    }
+
+    #[test]
+    fn default_bindings_round_trip() {
+        for action in DEFAULT_ACTIONS {
+            for &binding in action.bindings {
+                assert_eq!(Binding::decode(&binding.encode()), Some(binding));
+            }
+        }
+    }
+
+    #[test]
+    fn encoding_matches_saved_files() {
+        assert_eq!(Binding::Key(69).encode(), "key:69");
+        assert_eq!(Binding::MouseButton(2).encode(), "mouse:2");
+        assert_eq!(Binding::JoyButton(0).encode(), "joy_button:0");
+        assert_eq!(Binding::JoyAxis(1, -1).encode(), "joy_axis:1:-1");
+    }
+
+    #[test]
+    fn garbage_is_not_decoded() {
+        let garbage = [
+            "",
+            "key",
+            "key:",
+            "key:escape",
+            "key:1:2",
+            "mouse:1.5",
+            "joy_axis:1",
+            "joy_axis:1:up",
+            "joy_axis:1:-1:0",
+            "pedal:3",
+            ":5",
+        ];
+
+        for encoded in &garbage {
+            assert_eq!(Binding::decode(encoded), None, "{:?}", encoded);
+        }
+    }
+
+    #[test]
+    fn keyboard_mouse_and_gamepad_are_separate_devices() {
+        assert_eq!(Binding::Key(69).device(), Device::Keyboard);
+        assert_eq!(Binding::MouseButton(2).device(), Device::Mouse);
+        assert_eq!(Binding::JoyButton(0).device(), Device::Joypad);
+        assert_eq!(Binding::JoyAxis(1, -1).device(), Device::Joypad);
+    }
+}
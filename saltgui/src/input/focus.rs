@@ -0,0 +1,244 @@
+use crate::{board::BoardShape, board_slot::SlotPos};
+use salt_engine::game_state::board::RowId;
+
+/// What the keyboard/gamepad cursor is pointing at.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Focus {
+    /// The player is using the mouse, or hasn't touched the keyboard/gamepad yet.
+    Unfocused,
+
+    /// The card at this index in the hand, counting from the left.
+    Hand(usize),
+
+    /// A slot on the board.
+    Board(SlotPos),
+}
+
+impl Default for Focus {
+    fn default() -> Self {
+        Focus::Unfocused
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// The slot the cursor lands on when it first moves from the hand onto the board.
+pub(crate) fn board_entry_pos() -> SlotPos {
+    SlotPos {
+        is_friendly: true,
+        row_id: RowId::FrontRow,
+        index: 0,
+    }
+}
+
+/// Moves the cursor one step through the hand, onto the board and back. Leaving the board
+/// lands on `hand_index`, e.g. the selected card. Until the board is built, and so has no
+/// `shape`, the cursor stays in the hand.
+pub(crate) fn move_focus(
+    focus: Focus,
+    direction: Direction,
+    hand_len: usize,
+    hand_index: usize,
+    shape: Option<&BoardShape>,
+) -> Focus {
+    match (focus, shape) {
+        (Focus::Unfocused, _) if hand_len > 0 => Focus::Hand(0),
+        (Focus::Unfocused, Some(_)) => Focus::Board(board_entry_pos()),
+        (Focus::Hand(_), Some(_)) if direction == Direction::Up => Focus::Board(board_entry_pos()),
+        (Focus::Hand(index), _) => Focus::Hand(move_in_hand(index, direction, hand_len)),
+        (Focus::Board(pos), Some(shape)) => match move_on_board(pos, direction, shape) {
+            Some(pos) => Focus::Board(pos),
+            None if hand_len > 0 => Focus::Hand(hand_index.min(hand_len - 1)),
+            None => Focus::Board(pos),
+        },
+        (Focus::Unfocused, None) => Focus::Unfocused,
+        (Focus::Board(_), None) if hand_len > 0 => Focus::Hand(hand_index.min(hand_len - 1)),
+        (Focus::Board(_), None) => Focus::Unfocused,
+    }
+}
+
+/// Moves a board cursor one step, staying within the board's shape.
+/// Returns `None` when moving down off the bottom row, i.e. back into the hand.
+pub(crate) fn move_on_board(
+    pos: SlotPos,
+    direction: Direction,
+    shape: &BoardShape,
+) -> Option<SlotPos> {
+    let board_rows = shape.display_rows();
+    let row_len = shape.slots_per_row();
+
+    let row = board_rows
+        .iter()
+        .position(|&(is_friendly, row_id)| is_friendly == pos.is_friendly && row_id == pos.row_id)
+        .unwrap_or(shape.rows_per_side());
+
+    let (row, index) = match direction {
+        Direction::Left => (row, pos.index.saturating_sub(1)),
+        Direction::Right => (row, (pos.index + 1).min(row_len.saturating_sub(1))),
+        Direction::Up => (row.saturating_sub(1), pos.index),
+        Direction::Down if row + 1 < board_rows.len() => (row + 1, pos.index),
+        Direction::Down => return None,
+    };
+
+    let (is_friendly, row_id) = *board_rows.get(row)?;
+
+    Some(SlotPos {
+        is_friendly,
+        row_id,
+        index,
+    })
+}
+
+/// Steps through the hand, wrapping around at either end.
+pub(crate) fn move_in_hand(index: usize, direction: Direction, hand_len: usize) -> usize {
+    if hand_len == 0 {
+        return 0;
+    }
+
+    match direction {
+        Direction::Left => (index + hand_len - 1) % hand_len,
+        Direction::Right => (index + 1) % hand_len,
+        Direction::Up | Direction::Down => index.min(hand_len - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape() -> BoardShape {
+        BoardShape::new(vec![RowId::FrontRow, RowId::BackRow], 4)
+    }
+
+    fn pos(is_friendly: bool, row_id: RowId, index: usize) -> SlotPos {
+        SlotPos {
+            is_friendly,
+            row_id,
+            index,
+        }
+    }
+
+    #[test]
+    fn board_cursor_stops_at_the_row_ends() {
+        let shape = shape();
+        let left_end = pos(true, RowId::FrontRow, 0);
+        let right_end = pos(true, RowId::FrontRow, 3);
+
+        assert_eq!(
+            move_on_board(left_end, Direction::Left, &shape),
+            Some(left_end)
+        );
+        assert_eq!(
+            move_on_board(right_end, Direction::Right, &shape),
+            Some(right_end)
+        );
+    }
+
+    #[test]
+    fn board_cursor_crosses_to_the_enemy_side_and_stops_at_the_top() {
+        let shape = shape();
+        let friendly_front = pos(true, RowId::FrontRow, 2);
+        let enemy_front = pos(false, RowId::FrontRow, 2);
+        let enemy_back = pos(false, RowId::BackRow, 2);
+
+        assert_eq!(
+            move_on_board(friendly_front, Direction::Up, &shape),
+            Some(enemy_front)
+        );
+        assert_eq!(
+            move_on_board(enemy_front, Direction::Up, &shape),
+            Some(enemy_back)
+        );
+        assert_eq!(
+            move_on_board(enemy_back, Direction::Up, &shape),
+            Some(enemy_back)
+        );
+    }
+
+    #[test]
+    fn moving_down_off_the_bottom_row_leaves_the_board() {
+        let shape = shape();
+        let bottom = pos(true, RowId::BackRow, 1);
+
+        assert_eq!(move_on_board(bottom, Direction::Down, &shape), None);
+    }
+
+    #[test]
+    fn hand_cursor_wraps_around() {
+        assert_eq!(move_in_hand(0, Direction::Left, 3), 2);
+        assert_eq!(move_in_hand(2, Direction::Right, 3), 0);
+        assert_eq!(move_in_hand(5, Direction::Down, 3), 2);
+    }
+
+    #[test]
+    fn empty_hand_cursor_stays_at_zero() {
+        for &direction in &[Direction::Left, Direction::Right, Direction::Up] {
+            assert_eq!(move_in_hand(0, direction, 0), 0);
+        }
+    }
+
+    #[test]
+    fn up_from_the_hand_enters_the_board() {
+        let shape = shape();
+
+        assert_eq!(
+            move_focus(Focus::Hand(2), Direction::Up, 3, 0, Some(&shape)),
+            Focus::Board(board_entry_pos())
+        );
+    }
+
+    #[test]
+    fn down_off_the_board_returns_to_the_given_hand_card() {
+        let shape = shape();
+        let bottom = Focus::Board(pos(true, RowId::BackRow, 0));
+
+        assert_eq!(
+            move_focus(bottom, Direction::Down, 3, 1, Some(&shape)),
+            Focus::Hand(1)
+        );
+        assert_eq!(
+            move_focus(bottom, Direction::Down, 0, 1, Some(&shape)),
+            bottom
+        );
+    }
+
+    #[test]
+    fn first_move_starts_in_the_hand_or_on_an_empty_hands_board() {
+        let shape = shape();
+
+        assert_eq!(
+            move_focus(Focus::Unfocused, Direction::Right, 3, 0, Some(&shape)),
+            Focus::Hand(0)
+        );
+        assert_eq!(
+            move_focus(Focus::Unfocused, Direction::Right, 0, 0, Some(&shape)),
+            Focus::Board(board_entry_pos())
+        );
+    }
+
+    #[test]
+    fn without_a_board_the_cursor_stays_in_the_hand() {
+        assert_eq!(
+            move_focus(Focus::Unfocused, Direction::Up, 3, 0, None),
+            Focus::Hand(0)
+        );
+        assert_eq!(
+            move_focus(Focus::Hand(1), Direction::Up, 3, 0, None),
+            Focus::Hand(1)
+        );
+        assert_eq!(
+            move_focus(Focus::Hand(2), Direction::Right, 3, 0, None),
+            Focus::Hand(0)
+        );
+        assert_eq!(
+            move_focus(Focus::Unfocused, Direction::Up, 0, 0, None),
+            Focus::Unfocused
+        );
+    }
+}
@@ -0,0 +1,191 @@
+pub(crate) mod bindings;
+pub(crate) mod focus;
+
+use bindings::Binding;
+use gdnative::api::{GlobalConstants, InputEventMouseButton, InputEventScreenTouch, InputMap};
+use gdnative::prelude::*;
+use log::info;
+
+/// Picks up a hand card, places it, or presses a button.
+pub(crate) const SELECT: &str = "saltgui_select";
+
+/// Moves the focus cursor left: to the previous hand card, or the previous slot in a row.
+pub(crate) const FOCUS_LEFT: &str = "saltgui_focus_left";
+
+/// Moves the focus cursor right: to the next hand card, or the next slot in a row.
+pub(crate) const FOCUS_RIGHT: &str = "saltgui_focus_right";
+
+/// Moves the focus cursor up a row, from the hand onto the board.
+pub(crate) const FOCUS_UP: &str = "saltgui_focus_up";
+
+/// Moves the focus cursor down a row, from the board back to the hand.
+pub(crate) const FOCUS_DOWN: &str = "saltgui_focus_down";
+
+/// Picks the focused hand card, or places the picked card in the focused slot.
+pub(crate) const CONFIRM: &str = "saltgui_confirm";
+
+/// Backs out of the current selection or drag.
+pub(crate) const CANCEL: &str = "saltgui_cancel";
+
+/// Shows the card under the pointer or cursor in the inspect overlay.
+pub(crate) const INSPECT: &str = "saltgui_inspect";
+
+/// Ends the player's turn.
+pub(crate) const END_TURN: &str = "saltgui_end_turn";
+
+/// Shows or hides the log console.
+pub(crate) const TOGGLE_CONSOLE: &str = "saltgui_toggle_console";
+
+/// Stick movement beyond this counts as a press.
+const JOY_AXIS_DEADZONE: f64 = 0.5;
+
+/// The device id Godot gives mouse events it emulates from touches.
+const EMULATED_MOUSE_DEVICE: i64 = -1;
+
+/// A gameplay action, with the localization key of the name shown on the rebinding screen
+/// and the bindings it starts with before the player changes anything.
+pub(crate) struct ActionDefault {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub bindings: &'static [Binding],
+}
+
+pub(crate) const DEFAULT_ACTIONS: &[ActionDefault] = &[
+    ActionDefault {
+        name: SELECT,
+        label: "action.select",
+        bindings: &[Binding::MouseButton(GlobalConstants::BUTTON_LEFT)],
+    },
+    ActionDefault {
+        name: FOCUS_LEFT,
+        label: "action.focus_left",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_LEFT),
+            Binding::Key(GlobalConstants::KEY_A),
+            Binding::JoyButton(GlobalConstants::JOY_DPAD_LEFT),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_0, -1),
+        ],
+    },
+    ActionDefault {
+        name: FOCUS_RIGHT,
+        label: "action.focus_right",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_RIGHT),
+            Binding::Key(GlobalConstants::KEY_D),
+            Binding::JoyButton(GlobalConstants::JOY_DPAD_RIGHT),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_0, 1),
+        ],
+    },
+    ActionDefault {
+        name: FOCUS_UP,
+        label: "action.focus_up",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_UP),
+            Binding::Key(GlobalConstants::KEY_W),
+            Binding::JoyButton(GlobalConstants::JOY_DPAD_UP),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_1, -1),
+        ],
+    },
+    ActionDefault {
+        name: FOCUS_DOWN,
+        label: "action.focus_down",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_DOWN),
+            Binding::Key(GlobalConstants::KEY_S),
+            Binding::JoyButton(GlobalConstants::JOY_DPAD_DOWN),
+            Binding::JoyAxis(GlobalConstants::JOY_AXIS_1, 1),
+        ],
+    },
+    ActionDefault {
+        name: CONFIRM,
+        label: "action.confirm",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_ENTER),
+            Binding::Key(GlobalConstants::KEY_SPACE),
+            Binding::JoyButton(GlobalConstants::JOY_XBOX_A),
+        ],
+    },
+    ActionDefault {
+        name: CANCEL,
+        label: "action.cancel",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_ESCAPE),
+            Binding::Key(GlobalConstants::KEY_BACKSPACE),
+            Binding::MouseButton(GlobalConstants::BUTTON_RIGHT),
+            Binding::JoyButton(GlobalConstants::JOY_XBOX_B),
+        ],
+    },
+    ActionDefault {
+        name: INSPECT,
+        label: "action.inspect",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_I),
+            // Right-click is bound to `CANCEL`, and inspects when there's nothing to cancel.
+            // See `is_inspect_click`.
+            Binding::MouseButton(GlobalConstants::BUTTON_MIDDLE),
+            Binding::JoyButton(GlobalConstants::JOY_XBOX_X),
+        ],
+    },
+    ActionDefault {
+        name: END_TURN,
+        label: "action.end_turn",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_E),
+            Binding::JoyButton(GlobalConstants::JOY_XBOX_Y),
+            Binding::JoyButton(GlobalConstants::JOY_START),
+        ],
+    },
+    ActionDefault {
+        name: TOGGLE_CONSOLE,
+        label: "action.toggle_console",
+        bindings: &[
+            Binding::Key(GlobalConstants::KEY_QUOTELEFT),
+            Binding::JoyButton(GlobalConstants::JOY_SELECT),
+        ],
+    },
+];
+
+/// Adds every gameplay action to Godot's `InputMap` with its default bindings,
+/// then applies whatever the player saved last time.
+/// Actions already defined, e.g. in the project settings, keep their bindings as defaults.
+/// Safe to call more than once, e.g. by any node that needs the actions from its `_ready`.
+pub(crate) fn init() {
+    let input_map = InputMap::godot_singleton();
+
+    for action in DEFAULT_ACTIONS {
+        if input_map.has_action(action.name) {
+            continue;
+        }
+
+        input_map.add_action(action.name, JOY_AXIS_DEADZONE);
+        bindings::apply(action.name, action.bindings);
+
+        info!("Registered input action {}", action.name);
+    }
+
+    bindings::load_saved();
+}
+
+/// Whether a mouse event was emulated by Godot from a touch.
+/// Touches are handled directly, so these are skipped to avoid acting on one tap twice.
+pub(crate) fn is_emulated_from_touch(event: &InputEvent) -> bool {
+    event.device() == EMULATED_MOUSE_DEVICE
+}
+
+/// Whether the event is a mouse press of `CANCEL`, i.e. a right-click by default.
+/// `World` lets these through only when there's no drag or selection to cancel,
+/// so a card or slot that receives one inspects itself.
+pub(crate) fn is_inspect_click(event: &InputEvent) -> bool {
+    event.cast::<InputEventMouseButton>().is_some()
+        && event.is_action_pressed(CANCEL, false)
+        && !is_emulated_from_touch(event)
+}
+
+/// Whether the event ends a tap or click, from either a touch or the `SELECT` action.
+pub(crate) fn is_select_released(event: &InputEvent) -> bool {
+    if let Some(touch) = event.cast::<InputEventScreenTouch>() {
+        return !touch.is_pressed();
+    }
+
+    event.is_action_released(SELECT) && !is_emulated_from_touch(event)
+}
@@ -8,25 +8,42 @@
 mod agent;
 mod board;
 mod board_slot;
+mod card_art;
 mod card_board_instance;
+mod card_inspect;
 mod card_instance;
+mod card_text;
 mod end_turn_button;
+mod error;
+mod fatal_error;
 mod gui_mana_counter;
 mod hand;
+mod input;
+mod locale;
+mod log_console;
+mod rebind_menu;
+mod scene_cache;
+mod scene_contract;
 mod textbox;
+mod toast;
 mod util;
 
 use agent::world::World;
 use board::Board;
 use board_slot::BoardSlot;
 use card_board_instance::CardBoardInstance;
+use card_inspect::CardInspect;
 use card_instance::CardInstance;
 use end_turn_button::EndTurnButton;
+use fatal_error::FatalErrorScreen;
 use gdnative::prelude::*;
 use godot_log::GodotLog;
 use gui_mana_counter::ManaCounter;
 use hand::Hand;
+use log_console::LogConsole;
+use rebind_menu::RebindMenu;
 use textbox::TextBox;
+use toast::Toasts;
 
 fn init(handle: InitHandle) {
     GodotLog::init();
@@ -39,6 +56,11 @@ fn init(handle: InitHandle) {
     handle.add_class::<ManaCounter>();
     handle.add_class::<CardBoardInstance>();
     handle.add_class::<Board>();
+    handle.add_class::<RebindMenu>();
+    handle.add_class::<CardInspect>();
+    handle.add_class::<FatalErrorScreen>();
+    handle.add_class::<LogConsole>();
+    handle.add_class::<Toasts>();
 }
 
 // Macro that creates the entry-points of the dynamic library.
@@ -65,3 +87,24 @@ impl From<SignalName> for GodotString {
         signal_name.0.into()
     }
 }
+
+/// Godot's built-in signal for a `BaseButton` being pressed.
+const PRESSED_SIGNAL: SignalName = SignalName("pressed");
+
+/// Godot's built-in signal for an input event on a `CollisionObject`.
+const INPUT_EVENT_SIGNAL: SignalName = SignalName("input_event");
+
+/// Godot's built-in signal for the mouse moving onto a `CollisionObject`.
+const MOUSE_ENTERED_SIGNAL: SignalName = SignalName("mouse_entered");
+
+/// Godot's built-in signal for the mouse moving off a `CollisionObject`.
+const MOUSE_EXITED_SIGNAL: SignalName = SignalName("mouse_exited");
+
+/// Godot's built-in signal for a `LineEdit`'s text being edited.
+const TEXT_CHANGED_SIGNAL: SignalName = SignalName("text_changed");
+
+/// Godot's built-in signal for Enter being pressed in a `LineEdit`.
+const TEXT_ENTERED_SIGNAL: SignalName = SignalName("text_entered");
+
+/// Godot's built-in signal for an `OptionButton` item being chosen.
+const ITEM_SELECTED_SIGNAL: SignalName = SignalName("item_selected");
@@ -0,0 +1,364 @@
+//! Per-language string tables for every piece of text the GUI draws itself.
+//!
+//! Tables are JSON files at `res://locale/<language>.json`, mapping a key to either a string
+//! or, for text that depends on a count, an object of plural forms (`zero`, `one`, `other`).
+//! Strings may contain `{name}` placeholders. Keys missing from a table fall back to English.
+
+use gdnative::api::{File, OS};
+use gdnative::prelude::*;
+use log::{info, warn};
+use serde::Deserialize;
+use std::{cell::RefCell, collections::HashMap, fmt::Display};
+
+const TABLE_DIR: &str = "res://locale";
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// The English text for every key, used when a table lacks a key or fails to load.
+const DEFAULT_STRINGS: &[(&str, &str)] = &[
+    ("turn.end", "End turn"),
+    ("turn.enemy", "(Enemy turn)"),
+    ("mana.display", "Mana: {available}/{limit}"),
+    ("inspect.cost", "Costs {cost} mana"),
+    ("inspect.base_stats", "Base: {attack}/{health}"),
+    ("inspect.owner.friendly", "Yours"),
+    ("inspect.owner.enemy", "Opponent's"),
+    ("rebind.listening", "Press a key or button..."),
+    ("rebind.reset", "Reset to defaults"),
+    ("error.toast", "Something went wrong: {error}"),
+    ("fatal.title", "Something went wrong"),
+    ("fatal.location", "At {location}, on the {thread} thread."),
+    ("fatal.details", "Details"),
+    ("fatal.quit", "Quit"),
+    ("console.filter", "Filter"),
+    (
+        "console.log_filter",
+        "Log filter, e.g. info,saltgui::agent=debug",
+    ),
+    ("console.level.error", "Errors"),
+    ("console.level.warn", "Warnings"),
+    ("console.level.info", "Info"),
+    ("console.level.debug", "Debug"),
+    ("console.level.trace", "Everything"),
+    ("binding.mouse_left", "Mouse Left"),
+    ("binding.mouse_right", "Mouse Right"),
+    ("binding.mouse_middle", "Mouse Middle"),
+    ("binding.mouse_button", "Mouse {button}"),
+    ("action.select", "Select"),
+    ("action.focus_left", "Move left"),
+    ("action.focus_right", "Move right"),
+    ("action.focus_up", "Move up"),
+    ("action.focus_down", "Move down"),
+    ("action.confirm", "Confirm"),
+    ("action.cancel", "Cancel"),
+    ("action.inspect", "Inspect card"),
+    ("action.end_turn", "End turn"),
+    ("action.toggle_console", "Log console"),
+];
+
+/// The English `one` and `other` forms for keys whose text depends on a count.
+const DEFAULT_PLURALS: &[(&str, &str, &str)] = &[(
+    "scene_contract.toast",
+    "Found {count} problem in the scenes; see the log",
+    "Found {count} problems in the scenes; see the log",
+)];
+
+/// One entry in a string table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Text(String),
+    Plural {
+        zero: Option<String>,
+        one: Option<String>,
+        other: String,
+    },
+}
+
+impl Entry {
+    /// The form matching `count`, or `other` when there's no count or no such form.
+    fn text(&self, count: Option<i64>) -> &str {
+        match self {
+            Entry::Text(text) => text,
+            Entry::Plural { zero, one, other } => {
+                let form = match count {
+                    Some(0) => zero.as_ref(),
+                    Some(1) => one.as_ref(),
+                    _ => None,
+                };
+                form.unwrap_or(other)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Locale {
+    table: HashMap<String, Entry>,
+
+    /// Bumped on every language change, so labels know to redraw themselves.
+    revision: u64,
+}
+
+thread_local! {
+    static LOCALE: RefCell<Locale> = RefCell::new(Locale::default());
+}
+
+/// Switches to the operating system's language, if there is a table for it.
+pub(crate) fn init() {
+    let os_locale = OS::godot_singleton().get_locale().to_string();
+    let language = os_locale.split('_').next().unwrap_or(DEFAULT_LANGUAGE);
+
+    if !set_language(language) {
+        set_language(DEFAULT_LANGUAGE);
+    }
+}
+
+/// Loads the string table for `language` and makes it current.
+/// Returns whether a table was found; if not, the current language is kept.
+pub(crate) fn set_language(language: &str) -> bool {
+    let table = if language == DEFAULT_LANGUAGE {
+        load_table(language).unwrap_or_default()
+    } else if let Some(table) = load_table(language) {
+        table
+    } else {
+        return false;
+    };
+
+    LOCALE.with(|locale| {
+        let mut locale = locale.borrow_mut();
+        locale.table = table;
+        locale.revision += 1;
+    });
+
+    info!("Switched language to {}", language);
+    true
+}
+
+/// Changes whenever the language does; compare against a stored value to know when to redraw.
+pub(crate) fn revision() -> u64 {
+    LOCALE.with(|locale| locale.borrow().revision)
+}
+
+/// The text for `key` in the current language.
+pub(crate) fn tr(key: &str) -> String {
+    tr_with(key, &[])
+}
+
+/// The text for `key`, with each `{name}` placeholder replaced by its value in `args`.
+pub(crate) fn tr_with(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill(&text(key, None), args)
+}
+
+/// The text for `key` in the plural form matching `count`, which is also available
+/// to the text as the `{count}` placeholder.
+pub(crate) fn tr_plural(key: &str, count: i64, args: &[(&str, &dyn Display)]) -> String {
+    let mut all_args: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+    all_args.extend_from_slice(args);
+
+    fill(&text(key, Some(count)), &all_args)
+}
+
+/// The text for `key` in the current language, falling back to English.
+fn text(key: &str, count: Option<i64>) -> String {
+    let text = LOCALE.with(|locale| {
+        locale
+            .borrow()
+            .table
+            .get(key)
+            .map(|entry| entry.text(count).to_string())
+    });
+
+    text.unwrap_or_else(|| default_text(key, count))
+}
+
+/// The English text for `key`, in the plural form matching `count` if it has plural forms.
+fn default_text(key: &str, count: Option<i64>) -> String {
+    let plural = DEFAULT_PLURALS
+        .iter()
+        .find(|(default_key, _, _)| *default_key == key)
+        .map(|(_, one, other)| if count == Some(1) { one } else { other });
+
+    plural
+        .or_else(|| {
+            DEFAULT_STRINGS
+                .iter()
+                .find(|(default_key, _)| *default_key == key)
+                .map(|(_, text)| text)
+        })
+        .map_or_else(
+            || {
+                warn!("No text for localization key {}", key);
+                key.to_string()
+            },
+            |text| (*text).to_string(),
+        )
+}
+
+/// Replaces each `{name}` placeholder with its value in `args`, in a single pass, so a value
+/// that itself contains braces, e.g. a card title, is copied through unchanged.
+/// Placeholders without a value are left as they are.
+fn fill(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after_brace = &rest[start + 1..];
+
+        let placeholder = after_brace.find('}').and_then(|end| {
+            let name = &after_brace[..end];
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (end, value))
+        });
+
+        if let Some((end, value)) = placeholder {
+            filled.push_str(&value.to_string());
+            rest = &after_brace[end + 1..];
+        } else {
+            filled.push('{');
+            rest = after_brace;
+        }
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+fn load_table(language: &str) -> Option<HashMap<String, Entry>> {
+    let path = format!("{}/{}.json", TABLE_DIR, language);
+
+    let file = File::new();
+    if !file.file_exists(&path) {
+        return None;
+    }
+
+    if let Err(e) = file.open(&path, File::READ) {
+        warn!("Could not open string table {}: {:?}", path, e);
+        return None;
+    }
+
+    let contents = file.get_as_text().to_string();
+    file.close();
+
+    match serde_json::from_str(&contents) {
+        Ok(table) => Some(table),
+        Err(e) => {
+            warn!("Could not parse string table {}: {}", path, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plural(zero: Option<&str>, one: Option<&str>, other: &str) -> Entry {
+        Entry::Plural {
+            zero: zero.map(str::to_string),
+            one: one.map(str::to_string),
+            other: other.to_string(),
+        }
+    }
+
+    #[test]
+    fn fill_replaces_every_placeholder() {
+        assert_eq!(
+            fill(
+                "Mana: {available}/{limit}",
+                &[("available", &3), ("limit", &5)]
+            ),
+            "Mana: 3/5"
+        );
+        assert_eq!(fill("{a}{a}", &[("a", &"x")]), "xx");
+    }
+
+    #[test]
+    fn fill_copies_values_through_unchanged() {
+        assert_eq!(
+            fill(
+                "Something went wrong: {error} ({count})",
+                &[("error", &"no node at {count}"), ("count", &2)]
+            ),
+            "Something went wrong: no node at {count} (2)"
+        );
+    }
+
+    #[test]
+    fn fill_keeps_unknown_placeholders_and_stray_braces() {
+        assert_eq!(fill("{unknown} {a}", &[("a", &1)]), "{unknown} 1");
+        assert_eq!(fill("{ {a} }", &[("a", &1)]), "{ 1 }");
+        assert_eq!(fill("trailing {", &[("a", &1)]), "trailing {");
+    }
+
+    #[test]
+    fn plural_forms_match_the_count() {
+        let entry = plural(Some("none"), Some("one"), "many");
+
+        assert_eq!(entry.text(Some(0)), "none");
+        assert_eq!(entry.text(Some(1)), "one");
+        assert_eq!(entry.text(Some(2)), "many");
+        assert_eq!(entry.text(Some(-1)), "many");
+        assert_eq!(entry.text(None), "many");
+    }
+
+    #[test]
+    fn missing_plural_forms_fall_back_to_other() {
+        let entry = plural(None, None, "many");
+
+        assert_eq!(entry.text(Some(0)), "many");
+        assert_eq!(entry.text(Some(1)), "many");
+    }
+
+    #[test]
+    fn plain_text_ignores_the_count() {
+        let entry = Entry::Text("text".to_string());
+
+        assert_eq!(entry.text(Some(1)), "text");
+        assert_eq!(entry.text(None), "text");
+    }
+
+    #[test]
+    fn tables_hold_text_and_plural_entries() {
+        let table: HashMap<String, Entry> = serde_json::from_str(
+            r#"{ "a": "text", "b": { "one": "{count} card", "other": "{count} cards" } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(table["a"].text(Some(1)), "text");
+        assert_eq!(table["b"].text(Some(1)), "{count} card");
+        assert_eq!(table["b"].text(Some(0)), "{count} cards");
+    }
+
+    #[test]
+    fn defaults_cover_plain_and_plural_keys() {
+        assert_eq!(default_text("turn.end", None), "End turn");
+        assert_eq!(
+            default_text("scene_contract.toast", Some(1)),
+            "Found {count} problem in the scenes; see the log"
+        );
+        assert_eq!(
+            default_text("scene_contract.toast", Some(3)),
+            "Found {count} problems in the scenes; see the log"
+        );
+        assert_eq!(default_text("no.such.key", None), "no.such.key");
+    }
+
+    #[test]
+    fn the_table_is_used_before_the_defaults() {
+        LOCALE.with(|locale| {
+            locale
+                .borrow_mut()
+                .table
+                .insert("turn.end".to_string(), Entry::Text("Fin".to_string()))
+        });
+
+        assert_eq!(tr("turn.end"), "Fin");
+        assert_eq!(tr("turn.enemy"), "(Enemy turn)");
+        assert_eq!(
+            tr_plural("scene_contract.toast", 1, &[]),
+            "Found 1 problem in the scenes; see the log"
+        );
+    }
+}
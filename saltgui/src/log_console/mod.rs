@@ -0,0 +1,340 @@
+use crate::{
+    card_text, input, locale, util, ITEM_SELECTED_SIGNAL, TEXT_CHANGED_SIGNAL, TEXT_ENTERED_SIGNAL,
+};
+use gdnative::api::{
+    GlobalConstants, HBoxContainer, LineEdit, OptionButton, PanelContainer, RichTextLabel,
+    VBoxContainer,
+};
+use gdnative::prelude::*;
+use godot_log::{GodotLog, LogEntry, RECENT_CAPACITY};
+use log::{info, Level};
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+
+/// The levels that can be picked as the least severe shown, with their localization keys.
+const LEVELS: &[(Level, &str)] = &[
+    (Level::Error, "console.level.error"),
+    (Level::Warn, "console.level.warn"),
+    (Level::Info, "console.level.info"),
+    (Level::Debug, "console.level.debug"),
+    (Level::Trace, "console.level.trace"),
+];
+
+/// Once this many lines have been appended since the output was last drawn in full,
+/// it's redrawn to drop the lines no longer kept.
+const MAX_APPENDED_LINES: usize = RECENT_CAPACITY * 2;
+
+/// How much of the screen's height the console covers, from the top.
+const HEIGHT_RATIO: f64 = 0.5;
+
+/// The console's controls, created once it's ready.
+struct Widgets {
+    panel: Ref<PanelContainer>,
+    filter: Ref<LineEdit>,
+    level: Ref<OptionButton>,
+
+    /// Edits `GodotLog`'s filter, which decides what reaches the Godot console.
+    log_filter: Ref<LineEdit>,
+    output: Ref<RichTextLabel>,
+}
+
+/// An overlay listing recent log records, toggled with `input::TOGGLE_CONSOLE`,
+/// so testers can follow the game without running it from the editor.
+#[derive(NativeClass)]
+#[inherit(Control)]
+pub struct LogConsole {
+    widgets: Option<Widgets>,
+
+    /// The records fetched from `godot_log` so far, oldest first.
+    entries: VecDeque<LogEntry>,
+
+    /// The sequence number of the next record to fetch.
+    next_sequence: u64,
+
+    /// Only records whose module or message contains this, ignoring case, are shown.
+    filter_text: String,
+
+    /// The least severe level shown.
+    min_level: Level,
+
+    /// Whether the output must be drawn in full, as the filters changed.
+    needs_render: bool,
+
+    /// How many records the output shows.
+    shown_lines: usize,
+
+    /// The `locale::revision` the text was last drawn in.
+    language_revision: u64,
+}
+
+impl LogConsole {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            widgets: None,
+            entries: VecDeque::new(),
+            next_sequence: 0,
+            filter_text: String::new(),
+            min_level: Level::Info,
+            needs_render: true,
+            shown_lines: 0,
+            language_revision: locale::revision(),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.widgets
+            .as_ref()
+            .map_or(false, |w| unsafe { w.panel.assume_safe() }.is_visible())
+    }
+
+    fn toggle(&mut self) {
+        let widgets = if let Some(widgets) = &self.widgets {
+            widgets
+        } else {
+            return;
+        };
+
+        let panel = unsafe { widgets.panel.assume_safe() };
+        let is_open = !panel.is_visible();
+        panel.set_visible(is_open);
+
+        if is_open {
+            unsafe { widgets.filter.assume_safe() }.grab_focus();
+        }
+    }
+
+    /// Takes any records logged since the last fetch, adding those that pass the filters
+    /// to the end of the output.
+    fn fetch_records(&mut self) {
+        let new_entries = godot_log::recent_records(self.next_sequence);
+
+        if let Some(last) = new_entries.last() {
+            self.next_sequence = last.sequence + 1;
+        }
+
+        if !self.needs_render {
+            self.append(&new_entries);
+        }
+
+        self.entries.extend(new_entries);
+
+        while self.entries.len() > RECENT_CAPACITY {
+            self.entries.pop_front();
+        }
+    }
+
+    fn is_shown(&self, entry: &LogEntry) -> bool {
+        if entry.level > self.min_level {
+            return false;
+        }
+
+        let filter = self.filter_text.to_lowercase();
+
+        filter.is_empty()
+            || entry.module_path.to_lowercase().contains(&filter)
+            || entry.message.to_lowercase().contains(&filter)
+    }
+
+    /// Adds the records that pass the filters to the end of the output.
+    fn append(&mut self, entries: &[LogEntry]) {
+        let output = if let Some(widgets) = &self.widgets {
+            widgets.output
+        } else {
+            return;
+        };
+        let output = unsafe { output.assume_safe() };
+
+        let lines = entries
+            .iter()
+            .filter(|entry| self.is_shown(entry))
+            .map(format_entry)
+            .collect::<Vec<_>>();
+
+        for line in lines {
+            let separator = if self.shown_lines == 0 { "" } else { "\n" };
+            let _ = output.append_bbcode(format!("{}{}", separator, line));
+            self.shown_lines += 1;
+        }
+
+        if self.shown_lines > MAX_APPENDED_LINES {
+            self.needs_render = true;
+        }
+    }
+
+    /// Draws every kept record that passes the filters, replacing the output.
+    fn render(&mut self) {
+        let widgets = if let Some(widgets) = &self.widgets {
+            widgets
+        } else {
+            return;
+        };
+
+        let lines = self
+            .entries
+            .iter()
+            .filter(|entry| self.is_shown(entry))
+            .map(format_entry)
+            .collect::<Vec<_>>();
+
+        self.shown_lines = lines.len();
+        unsafe { widgets.output.assume_safe() }.set_bbcode(lines.join("\n"));
+        self.needs_render = false;
+    }
+
+    /// Names each level option in the current language.
+    fn refresh_labels(&self) {
+        if let Some(widgets) = &self.widgets {
+            let level = unsafe { widgets.level.assume_safe() };
+
+            for (index, (_, key)) in (0..).zip(LEVELS) {
+                level.set_item_text(index, locale::tr(key));
+            }
+
+            unsafe { widgets.filter.assume_safe() }.set_placeholder(locale::tr("console.filter"));
+            unsafe { widgets.log_filter.assume_safe() }
+                .set_placeholder(locale::tr("console.log_filter"));
+        }
+    }
+}
+
+#[methods]
+impl LogConsole {
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        // Only the panel takes clicks, and only while it's open.
+        owner.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+        owner.set_anchors_preset(Control::PRESET_WIDE, false);
+
+        let panel = PanelContainer::new();
+        panel.set_anchors_preset(Control::PRESET_TOP_WIDE, false);
+        panel.set_anchor(GlobalConstants::MARGIN_BOTTOM, HEIGHT_RATIO, false, true);
+        panel.set_visible(false);
+
+        let rows = VBoxContainer::new();
+        let controls = HBoxContainer::new();
+
+        let filter = LineEdit::new().into_shared();
+        let filter_ref = unsafe { filter.assume_safe() };
+        filter_ref.set_h_size_flags(Control::SIZE_EXPAND_FILL);
+        util::connect_signal(filter_ref, TEXT_CHANGED_SIGNAL, owner, "on_filter_changed");
+        controls.add_child(filter, false);
+
+        let level = OptionButton::new().into_shared();
+        let level_ref = unsafe { level.assume_safe() };
+        for (index, (option_level, _)) in (0..).zip(LEVELS) {
+            level_ref.add_item("", index);
+
+            if *option_level == self.min_level {
+                level_ref.select(index);
+            }
+        }
+        util::connect_signal(level_ref, ITEM_SELECTED_SIGNAL, owner, "on_level_selected");
+        controls.add_child(level, false);
+
+        let log_filter = LineEdit::new().into_shared();
+        let log_filter_ref = unsafe { log_filter.assume_safe() };
+        log_filter_ref.set_h_size_flags(Control::SIZE_EXPAND_FILL);
+        log_filter_ref.set_text(GodotLog::filter_spec());
+        util::connect_signal(
+            log_filter_ref,
+            TEXT_ENTERED_SIGNAL,
+            owner,
+            "on_log_filter_entered",
+        );
+        controls.add_child(log_filter, false);
+
+        let output = RichTextLabel::new();
+        output.set_use_bbcode(true);
+        output.set_scroll_follow(true);
+        output.set_selection_enabled(true);
+        output.set_v_size_flags(Control::SIZE_EXPAND_FILL);
+        let output = output.into_shared();
+
+        rows.add_child(controls, false);
+        rows.add_child(output, false);
+        panel.add_child(rows, false);
+
+        let panel = panel.into_shared();
+        owner.add_child(panel, false);
+
+        self.widgets = Some(Widgets {
+            panel,
+            filter,
+            level,
+            log_filter,
+            output,
+        });
+
+        self.refresh_labels();
+        GodotLog::set_recent_level(self.min_level.to_level_filter());
+    }
+
+    /// Collects new records every frame, but only redraws in full while the console is open.
+    #[export]
+    fn _process(&mut self, _owner: TRef<Control>, _delta: f64) {
+        self.fetch_records();
+
+        let revision = locale::revision();
+        if revision != self.language_revision {
+            self.language_revision = revision;
+            self.refresh_labels();
+        }
+
+        if self.needs_render && self.is_open() {
+            self.render();
+        }
+    }
+
+    /// Toggles before anything else sees the press, so it works while typing a filter too.
+    #[export]
+    fn _input(&mut self, owner: TRef<Control>, event: Ref<InputEvent>) {
+        let event = unsafe { event.assume_safe() };
+
+        if !event.is_action_pressed(input::TOGGLE_CONSOLE, false) {
+            return;
+        }
+
+        self.toggle();
+
+        if let Some(tree) = owner.get_tree() {
+            unsafe { tree.assume_safe() }.set_input_as_handled();
+        }
+    }
+
+    #[export]
+    fn on_filter_changed(&mut self, _owner: TRef<Control>, text: String) {
+        self.filter_text = text;
+        self.needs_render = true;
+    }
+
+    /// Applies the typed filter to what's printed to the Godot console.
+    #[export]
+    fn on_log_filter_entered(&mut self, _owner: TRef<Control>, spec: String) {
+        GodotLog::set_filter(&spec);
+        info!("Log filter set to {:?}", spec);
+    }
+
+    #[export]
+    fn on_level_selected(&mut self, _owner: TRef<Control>, index: i64) {
+        if let Some((level, _)) = usize::try_from(index).ok().and_then(|i| LEVELS.get(i)) {
+            self.min_level = *level;
+            self.needs_render = true;
+            GodotLog::set_recent_level(level.to_level_filter());
+        }
+    }
+}
+
+/// One record as a line of BBCode, with errors and warnings colored.
+fn format_entry(entry: &LogEntry) -> String {
+    let line = card_text::escape(&format!(
+        "[{}][{}][{}] {}",
+        entry.level, entry.thread, entry.module_path, entry.message
+    ));
+
+    match entry.level {
+        Level::Error => format!("[color=#ff6b6b]{}[/color]", line),
+        Level::Warn => format!("[color=#ffd166]{}[/color]", line),
+        Level::Info => line,
+        Level::Debug | Level::Trace => format!("[color=#9a9a9a]{}[/color]", line),
+    }
+}
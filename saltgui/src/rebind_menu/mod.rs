@@ -0,0 +1,185 @@
+use crate::{
+    input::{
+        self,
+        bindings::{self, Binding},
+        DEFAULT_ACTIONS,
+    },
+    locale, util, PRESSED_SIGNAL,
+};
+use gdnative::api::{Button, HBoxContainer, InputEventMouseMotion, Label, VBoxContainer};
+use gdnative::prelude::*;
+use log::info;
+
+const LISTENING_TEXT: &str = "rebind.listening";
+const RESET_TEXT: &str = "rebind.reset";
+
+/// Stops listening for a new binding, rather than being bound itself.
+const STOP_LISTENING_ACTION: &str = "ui_cancel";
+
+/// A settings screen listing every gameplay action with its bindings.
+/// Pressing an action's button waits for the next key, mouse or gamepad input and binds it.
+/// `ui_cancel` stops waiting without changing anything.
+#[derive(NativeClass)]
+#[inherit(Control)]
+pub struct RebindMenu {
+    /// Each action's name, and the button showing its bindings.
+    buttons: Vec<(&'static str, Ref<Button>)>,
+
+    /// Each action's name label, and the localization key of its text.
+    labels: Vec<(&'static str, Ref<Label>)>,
+
+    reset_button: Option<Ref<Button>>,
+
+    /// The `locale::revision` the text was last drawn in.
+    language_revision: u64,
+
+    /// The action waiting for its new binding, if any.
+    listening_for: Option<&'static str>,
+}
+
+impl RebindMenu {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            buttons: vec![],
+            labels: vec![],
+            reset_button: None,
+            language_revision: locale::revision(),
+            listening_for: None,
+        }
+    }
+
+    /// Shows each action's current bindings on its button.
+    fn refresh(&self) {
+        for (action, button) in &self.buttons {
+            let text = if self.listening_for == Some(*action) {
+                locale::tr(LISTENING_TEXT)
+            } else {
+                bindings::current(action)
+                    .into_iter()
+                    .map(Binding::describe)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+
+            unsafe { button.assume_safe() }.set_text(text);
+        }
+
+        for (label_key, label) in &self.labels {
+            unsafe { label.assume_safe() }.set_text(locale::tr(label_key));
+        }
+
+        if let Some(reset_button) = &self.reset_button {
+            unsafe { reset_button.assume_safe() }.set_text(locale::tr(RESET_TEXT));
+        }
+    }
+}
+
+#[methods]
+impl RebindMenu {
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        // Readied before `World`, so the actions may not be registered yet.
+        input::init();
+
+        let rows = VBoxContainer::new();
+
+        for action in DEFAULT_ACTIONS {
+            let row = HBoxContainer::new();
+
+            let label = Label::new().into_shared();
+            row.add_child(label, false);
+            self.labels.push((action.label, label));
+
+            let button = Button::new().into_shared();
+            let binds = VariantArray::new();
+            binds.push(GodotString::from(action.name));
+            util::connect_signal_with_binds(
+                unsafe { button.assume_safe() },
+                PRESSED_SIGNAL,
+                owner,
+                "on_rebind_pressed",
+                binds.into_shared(),
+            );
+            row.add_child(button, false);
+
+            rows.add_child(row, false);
+            self.buttons.push((action.name, button));
+        }
+
+        let reset = Button::new().into_shared();
+        util::connect_signal(
+            unsafe { reset.assume_safe() },
+            PRESSED_SIGNAL,
+            owner,
+            "on_reset_pressed",
+        );
+        rows.add_child(reset, false);
+        self.reset_button = Some(reset);
+
+        owner.add_child(rows, false);
+
+        self.refresh();
+    }
+
+    /// Redraws every label when the language changes.
+    #[export]
+    fn _process(&mut self, _owner: TRef<Control>, _delta: f64) {
+        let revision = locale::revision();
+
+        if revision != self.language_revision {
+            self.language_revision = revision;
+            self.refresh();
+        }
+    }
+
+    /// While waiting for a new binding, captures the next press before anything else sees it.
+    #[export]
+    fn _input(&mut self, owner: TRef<Control>, event: Ref<InputEvent>) {
+        let action = if let Some(action) = self.listening_for {
+            action
+        } else {
+            return;
+        };
+
+        let event = unsafe { event.assume_safe() };
+
+        if event.cast::<InputEventMouseMotion>().is_some() || !event.is_pressed() {
+            return;
+        }
+
+        if event.is_action_pressed(STOP_LISTENING_ACTION, false) {
+            info!("Stopped waiting for a new binding for {}", action);
+        } else if let Some(binding) = Binding::from_event(event) {
+            bindings::rebind(action, binding);
+        } else {
+            return;
+        }
+
+        self.listening_for = None;
+        self.refresh();
+
+        if let Some(tree) = owner.get_tree() {
+            unsafe { tree.assume_safe() }.set_input_as_handled();
+        }
+    }
+
+    #[export]
+    fn on_rebind_pressed(&mut self, _owner: TRef<Control>, action: Variant) {
+        let action = action.try_to_string().unwrap_or_default();
+
+        self.listening_for = DEFAULT_ACTIONS
+            .iter()
+            .find(|a| a.name == action)
+            .map(|a| a.name);
+
+        info!("Waiting for a new binding for {:?}", self.listening_for);
+        self.refresh();
+    }
+
+    #[export]
+    fn on_reset_pressed(&mut self, _owner: TRef<Control>) {
+        self.listening_for = None;
+        bindings::reset_to_defaults();
+        self.refresh();
+    }
+}
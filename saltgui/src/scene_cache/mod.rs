@@ -0,0 +1,335 @@
+//! The scenes instanced from code, each loaded once and kept for the rest of the game.
+//!
+//! A scene is loaded the first time it's instanced, or ahead of time on a background thread
+//! with `preload`. How long each load took is kept, so the startup cost shows up in the log.
+
+use crate::{
+    error::{short_type_name, GuiError},
+    util,
+};
+use crossbeam::channel::{self, Receiver, TryRecvError};
+use gdnative::prelude::*;
+use log::{info, warn};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const PRELOAD_THREAD_NAME: &str = "scene-preload";
+
+/// The project setting that turns `preload` off, when set to false.
+const PRELOAD_SETTING: &str = "saltgui/scenes/preload";
+
+/// How a scene came to be in the cache.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LoadedBy {
+    Preload,
+    FirstUse,
+}
+
+impl LoadedBy {
+    fn describe(self) -> &'static str {
+        match self {
+            LoadedBy::Preload => "preloaded",
+            LoadedBy::FirstUse => "loaded on first use",
+        }
+    }
+}
+
+/// How much one scene has cost so far.
+#[derive(Debug, Clone, Copy)]
+struct SceneMetrics {
+    loaded_by: LoadedBy,
+    load_time: Duration,
+    instances: u32,
+    instance_time: Duration,
+}
+
+/// A scene the preload thread has finished with, whether or not it loaded.
+struct Preloaded {
+    path: &'static str,
+    scene: Option<Ref<Resource, Shared>>,
+    load_time: Duration,
+}
+
+#[derive(Default)]
+struct Cache {
+    scenes: HashMap<&'static str, Ref<PackedScene, ThreadLocal>>,
+    metrics: HashMap<&'static str, SceneMetrics>,
+
+    /// The scenes the preload thread hasn't sent yet.
+    pending: Vec<&'static str>,
+    preloaded: Option<Receiver<Preloaded>>,
+}
+
+impl Cache {
+    fn insert(
+        &mut self,
+        path: &'static str,
+        scene: Ref<PackedScene, ThreadLocal>,
+        loaded_by: LoadedBy,
+        load_time: Duration,
+    ) {
+        self.scenes.insert(path, scene);
+        self.metrics.insert(
+            path,
+            SceneMetrics {
+                loaded_by,
+                load_time,
+                instances: 0,
+                instance_time: Duration::default(),
+            },
+        );
+    }
+
+    /// Caches the scenes the preload thread has sent, first waiting for `wait_for`
+    /// if it's one the thread hasn't got to yet.
+    fn receive(&mut self, wait_for: Option<&str>) {
+        let receiver = if let Some(receiver) = &self.preloaded {
+            receiver.clone()
+        } else {
+            return;
+        };
+
+        loop {
+            let is_waiting = wait_for.map_or(false, |path| self.pending.iter().any(|p| *p == path));
+
+            let next = if is_waiting {
+                receiver.recv().map_err(|_| TryRecvError::Disconnected)
+            } else {
+                receiver.try_recv()
+            };
+
+            match next {
+                Ok(preloaded) => self.insert_preloaded(preloaded),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => break,
+            }
+        }
+
+        if !self.pending.is_empty() {
+            warn!(
+                "Stopped preloading before {}; they will be loaded on first use",
+                self.pending.join(", ")
+            );
+        }
+
+        self.pending.clear();
+        self.preloaded = None;
+        self.log_preload_total();
+    }
+
+    fn insert_preloaded(&mut self, preloaded: Preloaded) {
+        let Preloaded {
+            path,
+            scene,
+            load_time,
+        } = preloaded;
+
+        self.pending.retain(|pending| *pending != path);
+
+        let scene =
+            scene.and_then(|scene| unsafe { scene.assume_thread_local() }.cast::<PackedScene>());
+
+        if let Some(scene) = scene {
+            info!("Preloaded {} in {}", path, format_duration(load_time));
+            self.insert(path, scene, LoadedBy::Preload, load_time);
+        } else {
+            warn!("Could not preload {}; it will be loaded on first use", path);
+        }
+    }
+
+    fn log_preload_total(&self) {
+        let preloads = self
+            .metrics
+            .values()
+            .filter(|metrics| metrics.loaded_by == LoadedBy::Preload);
+
+        let (count, total) = preloads.fold((0, Duration::default()), |(count, total), m| {
+            (count + 1, total + m.load_time)
+        });
+
+        info!("Preloaded {} scene(s) in {}", count, format_duration(total));
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<Cache> = RefCell::new(Cache::default());
+}
+
+/// Starts loading `paths` on a background thread, unless turned off in the project settings.
+/// A scene instanced before the thread gets to it is waited for, rather than loaded twice.
+pub(crate) fn preload(paths: &[&'static str]) {
+    if !is_preload_enabled() {
+        info!("Scene preloading is turned off by {}", PRELOAD_SETTING);
+        return;
+    }
+
+    let paths = CACHE.with(|cache| {
+        let cache = cache.borrow();
+
+        paths
+            .iter()
+            .copied()
+            .filter(|path| !cache.scenes.contains_key(path) && !cache.pending.contains(path))
+            .collect::<Vec<_>>()
+    });
+
+    if paths.is_empty() {
+        return;
+    }
+
+    let (sender, receiver) = channel::unbounded();
+    let thread_paths = paths.clone();
+
+    let spawned = thread::Builder::new()
+        .name(PRELOAD_THREAD_NAME.to_string())
+        .spawn(move || {
+            let loader = ResourceLoader::godot_singleton();
+
+            for path in thread_paths {
+                let start = Instant::now();
+                let scene = loader.load(path, "PackedScene", false);
+
+                let preloaded = Preloaded {
+                    path,
+                    scene,
+                    load_time: start.elapsed(),
+                };
+
+                if sender.send(preloaded).is_err() {
+                    break;
+                }
+            }
+        });
+
+    if let Err(e) = spawned {
+        warn!("Could not start the scene preload thread: {}", e);
+        return;
+    }
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.pending.extend(paths);
+        cache.preloaded = Some(receiver);
+    });
+}
+
+/// Caches any scenes the preload thread has finished. Called every frame by `World`.
+pub(crate) fn poll() {
+    CACHE.with(|cache| cache.borrow_mut().receive(None));
+}
+
+/// Whether the preload thread still has scenes to send.
+pub(crate) fn is_preloading() -> bool {
+    CACHE.with(|cache| !cache.borrow().pending.is_empty())
+}
+
+/// Instances the scene at `path`, loading it first if it isn't cached yet.
+pub(crate) fn instance<TRoot>(path: &'static str) -> Result<Ref<TRoot, Unique>, GuiError>
+where
+    TRoot: gdnative::GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
+{
+    let scene = scene(path)?;
+
+    // The cache isn't borrowed here, as instancing runs the scene's scripts' constructors.
+    let start = Instant::now();
+    let instance = util::instance_scene::<TRoot>(&scene, path);
+    let elapsed = start.elapsed();
+
+    CACHE.with(|cache| {
+        if let Some(metrics) = cache.borrow_mut().metrics.get_mut(path) {
+            metrics.instances += 1;
+            metrics.instance_time += elapsed;
+        }
+    });
+
+    instance
+}
+
+/// Instances the scene at `path`, as the `T` script on its root.
+pub(crate) fn instance_script<T>(path: &'static str) -> Result<Instance<T, Unique>, GuiError>
+where
+    T: NativeClass,
+    T::Base: gdnative::GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
+{
+    let root = instance::<T::Base>(path)?;
+
+    Instance::try_from_base(root).map_err(|root| {
+        root.free();
+        GuiError::MissingScript {
+            path: path.to_string(),
+            rust_type: short_type_name::<T>(),
+            godot_base: short_type_name::<T::Base>(),
+        }
+    })
+}
+
+/// Logs what each cached scene has cost to load and instance.
+pub(crate) fn log_metrics() {
+    CACHE.with(|cache| {
+        let cache = cache.borrow();
+
+        let mut paths = cache.metrics.keys().collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            let metrics = &cache.metrics[path];
+            let average = metrics
+                .instance_time
+                .checked_div(metrics.instances)
+                .unwrap_or_default();
+
+            info!(
+                "{}: {} in {}, instanced {} time(s), taking {} on average",
+                path,
+                metrics.loaded_by.describe(),
+                format_duration(metrics.load_time),
+                metrics.instances,
+                format_duration(average),
+            );
+        }
+    });
+}
+
+/// The scene at `path`, from the cache if possible. Waits for the preload thread if it
+/// hasn't sent the scene yet, so check `is_preloading` first to avoid blocking.
+pub(crate) fn scene(path: &'static str) -> Result<Ref<PackedScene, ThreadLocal>, GuiError> {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.receive(Some(path));
+
+        if let Some(scene) = cache.scenes.get(path) {
+            return Ok(scene.clone());
+        }
+
+        let start = Instant::now();
+        let scene = util::load_scene(path).ok_or_else(|| GuiError::SceneNotLoaded {
+            path: path.to_string(),
+        })?;
+        let load_time = start.elapsed();
+
+        info!(
+            "Loaded {} on first use in {}",
+            path,
+            format_duration(load_time)
+        );
+        cache.insert(path, scene.clone(), LoadedBy::FirstUse, load_time);
+
+        Ok(scene)
+    })
+}
+
+fn is_preload_enabled() -> bool {
+    let settings = ProjectSettings::godot_singleton();
+
+    !settings.has_setting(PRELOAD_SETTING)
+        || settings
+            .get_setting(PRELOAD_SETTING)
+            .try_to_bool()
+            .unwrap_or(true)
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.)
+}
@@ -0,0 +1,242 @@
+//! The nodes each scene's code expects to find, checked when the game starts.
+//!
+//! Each module declares a `SceneContract` next to the paths it uses, so renaming or retyping
+//! a node in the editor shows up as one readable report instead of a panic somewhere later.
+
+use crate::{
+    agent::world, board_slot, card_board_instance, card_instance, locale, scene_cache, toast, util,
+};
+use gdnative::api::NativeScript;
+use gdnative::prelude::*;
+use log::{error, info};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A node a scene's code expects, relative to the scene's root.
+pub(crate) struct RequiredNode {
+    pub path: &'static str,
+
+    /// The Godot class the node must be, or inherit from.
+    pub class: &'static str,
+
+    /// The NativeScript class the node must have attached, if any.
+    pub script: Option<&'static str>,
+
+    /// What the node itself must contain, e.g. when it is an instance of another scene.
+    pub contains: Option<&'static SceneContract>,
+}
+
+impl RequiredNode {
+    pub const fn new(path: &'static str, class: &'static str) -> Self {
+        Self {
+            path,
+            class,
+            script: None,
+            contains: None,
+        }
+    }
+
+    pub const fn with_script(mut self, script: &'static str) -> Self {
+        self.script = Some(script);
+        self
+    }
+
+    pub const fn containing(mut self, contract: &'static SceneContract) -> Self {
+        self.contains = Some(contract);
+        self
+    }
+}
+
+/// Every node a scene's code expects.
+pub(crate) struct SceneContract {
+    /// What the report calls the scene.
+    pub name: &'static str,
+
+    /// The scene file, for scenes that are instanced from code rather than already loaded.
+    pub scene: Option<&'static str>,
+    pub nodes: &'static [RequiredNode],
+}
+
+/// One way a scene breaks its contract.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Violation {
+    /// The scene file couldn't be loaded or instanced, for this reason.
+    SceneNotLoaded(String),
+    Missing(String),
+    WrongClass {
+        path: String,
+        expected: &'static str,
+        actual: String,
+    },
+    WrongScript {
+        path: String,
+        expected: &'static str,
+        actual: Option<String>,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::SceneNotLoaded(reason) => write!(f, "{}", reason),
+            Violation::Missing(path) => write!(f, "{} is missing", path),
+            Violation::WrongClass {
+                path,
+                expected,
+                actual,
+            } => write!(f, "{} is a {}, but should be a {}", path, actual, expected),
+            Violation::WrongScript {
+                path,
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "{} has the {} script, but needs {}",
+                path, actual, expected
+            ),
+            Violation::WrongScript {
+                path,
+                expected,
+                actual: None,
+            } => write!(f, "{} has no script, but needs {}", path, expected),
+        }
+    }
+}
+
+/// The contracts of the scenes `World` instances from code.
+const INSTANCED_SCENES: &[&SceneContract] = &[
+    &card_instance::SCENE_CONTRACT,
+    &card_board_instance::SCENE_CONTRACT,
+    &board_slot::SCENE_CONTRACT,
+];
+
+/// Checks the world's own nodes. Run from `World::_enter_tree`, before those nodes are readied.
+/// Returns whether everything was as expected.
+pub(crate) fn validate_world(world: TRef<Node>) -> bool {
+    let violations = check(world, &world::SCENE_CONTRACT);
+    report(&[(world::SCENE_CONTRACT.name, violations)])
+}
+
+/// Checks every scene `World` instances from code. Run once `scene_cache` is done preloading,
+/// so the check doesn't wait on it. Returns whether everything was as expected.
+pub(crate) fn validate_instanced_scenes() -> bool {
+    let checked = INSTANCED_SCENES
+        .iter()
+        .map(|contract| (contract.name, check_scene(contract)))
+        .collect::<Vec<_>>();
+
+    report(&checked)
+}
+
+/// Logs every problem found in one entry, and shows their count in a toast.
+/// Returns whether there were none.
+fn report(checked: &[(&str, Vec<Violation>)]) -> bool {
+    let count = checked.iter().map(|(_, v)| v.len()).sum::<usize>();
+
+    if count == 0 {
+        let names = checked.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        info!("{} have the nodes their code expects.", names.join(", "));
+        return true;
+    }
+
+    let mut text = format!("Found {} problem(s) in the scenes:", count);
+
+    for (name, violations) in checked.iter().filter(|(_, v)| !v.is_empty()) {
+        text.push_str(&format!("\n  {}:", name));
+
+        for violation in violations {
+            text.push_str(&format!("\n    - {}", violation));
+        }
+    }
+
+    error!("{}", text);
+    toast::show(locale::tr_plural(
+        "scene_contract.toast",
+        i64::try_from(count).unwrap_or(i64::MAX),
+        &[],
+    ));
+
+    false
+}
+
+/// Instances a contract's scene, checks the instance, and frees it.
+/// The instance isn't counted in the cache's metrics, as the game never uses it.
+fn check_scene(contract: &SceneContract) -> Vec<Violation> {
+    let path = if let Some(path) = contract.scene {
+        path
+    } else {
+        return vec![];
+    };
+
+    let instance =
+        scene_cache::scene(path).and_then(|scene| util::instance_scene::<Node>(&scene, path));
+
+    match instance {
+        Ok(root) => {
+            let root = root.into_shared();
+            let root = unsafe { root.assume_safe() };
+            let violations = check(root, contract);
+            root.free();
+            violations
+        }
+        Err(e) => vec![Violation::SceneNotLoaded(e.to_string())],
+    }
+}
+
+/// Checks that every node in `contract` is under `root`, with the right class and script.
+pub(crate) fn check(root: TRef<Node>, contract: &SceneContract) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    check_into(root, contract, "", &mut violations);
+    violations
+}
+
+fn check_into(
+    root: TRef<Node>,
+    contract: &SceneContract,
+    prefix: &str,
+    violations: &mut Vec<Violation>,
+) {
+    for required in contract.nodes {
+        let full_path = format!("{}{}", prefix, required.path);
+
+        let node = if let Some(node) = root.get_node(required.path) {
+            unsafe { node.assume_safe() }
+        } else {
+            violations.push(Violation::Missing(full_path));
+            continue;
+        };
+
+        if !node.is_class(required.class) {
+            violations.push(Violation::WrongClass {
+                path: full_path.clone(),
+                expected: required.class,
+                actual: node.get_class().to_string(),
+            });
+        }
+
+        if let Some(expected) = required.script {
+            let actual = script_class(node);
+
+            if actual.as_deref() != Some(expected) {
+                violations.push(Violation::WrongScript {
+                    path: full_path.clone(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        if let Some(contains) = required.contains {
+            check_into(node, contains, &format!("{}/", full_path), violations);
+        }
+    }
+}
+
+/// The class name of a node's NativeScript, if it has one.
+fn script_class(node: TRef<Node>) -> Option<String> {
+    let script = node.get_script()?;
+    let script = unsafe { script.assume_safe() };
+    let script = script.cast::<NativeScript>()?;
+
+    Some(script.class_name().to_string())
+}
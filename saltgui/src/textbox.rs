@@ -1,9 +1,19 @@
-use crate::util::NodeRef;
+use crate::{
+    error::OrReport,
+    scene_contract::{RequiredNode, SceneContract},
+    util::NodeRef,
+};
 use gdnative::{api::RichTextLabel, prelude::*};
 use log::{info, warn};
 
 const LABEL_PATH: &str = "Viewport/GUI/Panel/RichTextLabel";
 
+pub(crate) const SCENE_CONTRACT: SceneContract = SceneContract {
+    name: "TextBox",
+    scene: None,
+    nodes: &[RequiredNode::new(LABEL_PATH, "RichTextLabel")],
+};
+
 #[derive(NativeClass, Debug)]
 #[register_with(Self::register)]
 #[inherit(Spatial)]
@@ -25,27 +35,50 @@ impl TextBox {
 impl TextBox {
     #[export]
     fn _ready(&mut self, owner: TRef<Spatial>) {
-        self.textbox.init_from_parent(owner);
-        self.is_ready = true;
+        self.is_ready = self
+            .textbox
+            .try_init_from_parent(owner)
+            .or_report()
+            .is_some();
     }
 
     pub fn set_text(&self, text: &str) {
         if self.is_ready {
             info!("Setting textbox text to: {}", text);
-            self.textbox.resolve_ref().set_text(text);
+            self.textbox
+                .try_resolve()
+                .map(|t| t.set_text(text))
+                .or_report();
         } else {
             warn!("set_text invoked when TextBox is not yet ready");
         }
     }
 
-    pub fn get_text(&self) -> GodotString {
+    /// Sets the text as BBCode, e.g. from `card_text`.
+    pub fn set_bbcode(&self, bbcode: &str) {
         if self.is_ready {
-            self.textbox.resolve_ref().text()
+            self.textbox
+                .try_resolve()
+                .map(|t| {
+                    t.set_use_bbcode(true);
+                    t.set_bbcode(bbcode);
+                })
+                .or_report();
         } else {
-            "<TextBox not yet ready>".into()
+            warn!("set_bbcode invoked when TextBox is not yet ready");
         }
     }
 
+    pub fn get_text(&self) -> GodotString {
+        let text = if self.is_ready {
+            self.textbox.try_resolve().map(|t| t.text()).or_report()
+        } else {
+            None
+        };
+
+        text.unwrap_or_else(|| "<TextBox not yet ready>".into())
+    }
+
     fn register(builder: &ClassBuilder<Self>) {
         builder
             .add_property::<GodotString>("text")
@@ -0,0 +1,108 @@
+use gdnative::api::{BoxContainer, GlobalConstants, Label, VBoxContainer};
+use gdnative::prelude::*;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// How long a toast stays on screen, in seconds.
+const TOAST_SECONDS: f64 = 5.;
+
+/// Older toasts are dropped once this many are showing.
+const MAX_TOASTS: usize = 4;
+
+/// Distance from the bottom of the screen to the newest toast.
+const BOTTOM_MARGIN: f64 = 96.;
+
+thread_local! {
+    /// Messages waiting for the `Toasts` node to show them.
+    static QUEUED: RefCell<VecDeque<String>> = RefCell::new(VecDeque::new());
+}
+
+/// Shows a short message near the bottom of the screen.
+/// Any node may call this; the `Toasts` node picks it up on its next frame.
+pub(crate) fn show(message: String) {
+    QUEUED.with(|queued| queued.borrow_mut().push_back(message));
+}
+
+/// Brief messages stacked above the bottom of the screen, each removed after a few seconds.
+/// Created by `World`, rather than placed in the scene.
+#[derive(NativeClass)]
+#[inherit(Control)]
+pub struct Toasts {
+    rows: Option<Ref<VBoxContainer>>,
+
+    /// Each toast shown, oldest first, and the seconds it has left.
+    shown: VecDeque<(Ref<Label>, f64)>,
+}
+
+impl Toasts {
+    fn new(_owner: &Control) -> Self {
+        Self {
+            rows: None,
+            shown: VecDeque::new(),
+        }
+    }
+
+    /// Adds a `Toasts` node to `parent`.
+    pub(crate) fn add_to(parent: TRef<Node>) {
+        let toasts = Instance::<Self, Unique>::new().into_base();
+        toasts.set_name("Toasts");
+        parent.add_child(toasts, false);
+    }
+
+    fn remove_oldest(&mut self) {
+        if let Some((label, _)) = self.shown.pop_front() {
+            unsafe { label.assume_safe() }.queue_free();
+        }
+    }
+}
+
+#[methods]
+impl Toasts {
+    #[export]
+    fn _ready(&mut self, owner: TRef<Control>) {
+        owner.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+        owner.set_anchors_preset(Control::PRESET_WIDE, false);
+
+        let rows = VBoxContainer::new();
+        rows.set_mouse_filter(Control::MOUSE_FILTER_IGNORE);
+        rows.set_anchors_preset(Control::PRESET_WIDE, false);
+        rows.set_margin(GlobalConstants::MARGIN_BOTTOM, -BOTTOM_MARGIN);
+        // Newest at the bottom, with older toasts pushed up above it.
+        rows.set_alignment(BoxContainer::ALIGN_END);
+
+        let rows = rows.into_shared();
+        owner.add_child(rows, false);
+        self.rows = Some(rows);
+    }
+
+    #[export]
+    fn _process(&mut self, _owner: TRef<Control>, delta: f64) {
+        for (_, seconds_left) in &mut self.shown {
+            *seconds_left -= delta;
+        }
+
+        while self.shown.front().map_or(false, |(_, left)| *left <= 0.) {
+            self.remove_oldest();
+        }
+
+        let rows = if let Some(rows) = self.rows {
+            unsafe { rows.assume_safe() }
+        } else {
+            return;
+        };
+
+        while let Some(message) = QUEUED.with(|queued| queued.borrow_mut().pop_front()) {
+            if self.shown.len() == MAX_TOASTS {
+                self.remove_oldest();
+            }
+
+            let label = Label::new();
+            label.set_align(Label::ALIGN_CENTER);
+            label.set_text(message);
+            let label = label.into_shared();
+
+            rows.add_child(label, false);
+            self.shown.push_back((label, TOAST_SECONDS));
+        }
+    }
+}
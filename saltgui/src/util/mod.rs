@@ -1,6 +1,11 @@
 mod godot_extensions;
 
-use crate::SignalName;
+use crate::{
+    error::{short_type_name, GuiError},
+    SignalName,
+};
+use gdnative::api::{MeshInstance, QuadMesh, SpatialMaterial};
+use gdnative::nativescript::user_data::{Map, MapMut};
 use gdnative::prelude::*;
 use log::info;
 use std::ops::Deref;
@@ -14,18 +19,30 @@ pub(crate) fn load_scene(path: &str) -> Option<Ref<PackedScene, ThreadLocal>> {
     scene.cast::<PackedScene>()
 }
 
-/// Instance a scene.
-pub(crate) fn instance_scene<TRoot>(scene: &PackedScene) -> Ref<TRoot, Unique>
+/// Instance a loaded scene, failing instead of panicking if it can't.
+/// `path` is where the scene was loaded from, for the error.
+pub(crate) fn instance_scene<TRoot>(
+    scene: &PackedScene,
+    path: &str,
+) -> Result<Ref<TRoot, Unique>, GuiError>
 where
     TRoot: gdnative::GodotObject<RefKind = ManuallyManaged> + SubClass<Node>,
 {
     let instance = scene
         .instance(PackedScene::GEN_EDIT_STATE_DISABLED)
-        .unwrap();
+        .ok_or_else(|| GuiError::SceneNotLoaded {
+            path: path.to_string(),
+        })?;
 
     let instance = unsafe { instance.assume_unique() };
 
-    instance.try_cast::<TRoot>().unwrap()
+    instance.try_cast::<TRoot>().map_err(|instance| {
+        instance.free();
+        GuiError::WrongSceneRoot {
+            path: path.to_string(),
+            godot_base: short_type_name::<TRoot>(),
+        }
+    })
 }
 
 /// Connect a node to a signal on another node.
@@ -43,19 +60,114 @@ pub(crate) fn connect_signal<U: SubClass<Node>>(
         .expect("Failed binding signal");
 }
 
-pub(crate) fn get_as<T, B>(
+/// Connect a node to a signal on another node,
+/// passing `binds` to the target method after the signal's own arguments.
+pub(crate) fn connect_signal_with_binds<U: SubClass<Node>>(
+    from: impl Deref<Target = U>,
+    signal: SignalName,
+    target: impl AsArg<Object>,
+    target_method: impl Into<GodotString>,
+    binds: VariantArray<Shared>,
+) {
+    let target_method = target_method.into();
+
+    let node = from.upcast::<Node>();
+
+    node.connect(signal, target, target_method, binds, 0)
+        .expect("Failed binding signal");
+}
+
+/// Adds a flat, unshaded, translucent quad as a hidden child of `parent`.
+/// Returns the quad and its material, so callers can toggle and recolor it.
+pub(crate) fn add_overlay_quad(
+    parent: TRef<Spatial>,
+    name: &str,
+    size: Vector2,
+    offset: Vector3,
+) -> (Ref<MeshInstance, Shared>, Ref<SpatialMaterial, Shared>) {
+    let mesh = QuadMesh::new();
+    mesh.set_size(size);
+
+    let material = SpatialMaterial::new();
+    material.set_flag(SpatialMaterial::FLAG_UNSHADED, true);
+    material.set_feature(SpatialMaterial::FEATURE_TRANSPARENT, true);
+    let material = material.into_shared();
+
+    let quad = MeshInstance::new();
+    quad.set_name(name);
+    quad.set_mesh(mesh.into_shared());
+    quad.set_material_override(material.clone());
+    quad.set_translation(offset);
+    quad.set_visible(false);
+
+    let quad = quad.into_shared();
+    parent.add_child(quad, false);
+
+    (quad, material)
+}
+
+/// The node at `path`, relative to `owner`, as a `T`.
+pub(crate) fn get_node<'a, T>(
     path: impl AsRef<str>,
-    owner: TRef<Node>,
-) -> Option<RefInstance<T, Shared>>
+    owner: TRef<'a, Node>,
+) -> Result<TRef<'a, T>, GuiError>
+where
+    T: gdnative::GodotObject + SubClass<Node>,
+{
+    let path = path.as_ref();
+    let node = owner.get_node(path).ok_or_else(|| GuiError::NodeMissing {
+        path: path.to_string(),
+        godot_class: short_type_name::<T>(),
+    })?;
+
+    unsafe { node.assume_safe() }
+        .cast::<T>()
+        .ok_or_else(|| GuiError::WrongClass {
+            path: path.to_string(),
+            godot_class: short_type_name::<T>(),
+        })
+}
+
+/// The script instance on the node at `path`, relative to `owner`.
+pub(crate) fn get_as<'a, T, B>(
+    path: impl AsRef<str>,
+    owner: TRef<'a, Node>,
+) -> Result<RefInstance<'a, T, Shared>, GuiError>
 where
     T: NativeClass<Base = B>,
     B: SubClass<Node>,
 {
-    owner
-        .get_node(path)
-        .map(|r| unsafe { r.assume_safe() })
-        .map(|r| r.cast::<B>().unwrap())
-        .map(|r| r.cast_instance::<T>().unwrap())
+    let path = path.as_ref();
+    let node = owner.get_node(path).ok_or_else(|| GuiError::NodeNotFound {
+        path: path.to_string(),
+        rust_type: short_type_name::<T>(),
+        godot_base: short_type_name::<B>(),
+    })?;
+
+    cast_instance(path, unsafe { node.assume_safe() })
+}
+
+/// Casts a node to its base type, then to its script instance.
+fn cast_instance<'a, T, B>(
+    path: &str,
+    node: TRef<'a, Node>,
+) -> Result<RefInstance<'a, T, Shared>, GuiError>
+where
+    T: NativeClass<Base = B>,
+    B: SubClass<Node>,
+{
+    let base = node.cast::<B>().ok_or_else(|| GuiError::WrongBase {
+        path: path.to_string(),
+        rust_type: short_type_name::<T>(),
+        godot_base: short_type_name::<B>(),
+    })?;
+
+    base.cast_instance::<T>()
+        .ok_or_else(|| GuiError::MissingScript {
+            path: path.to_string(),
+            rust_type: short_type_name::<T>(),
+            godot_base: short_type_name::<B>(),
+        })
 }
 
 /// A longer-lived reference to a Godot Node object.
@@ -79,6 +191,17 @@ impl<T, N> Default for NodeRef<T, N> {
     }
 }
 
+impl<T, N> Clone for NodeRef<T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            _phantom: std::marker::PhantomData::default(),
+            _phantom_b: std::marker::PhantomData::default(),
+            reference: self.reference,
+            path: self.path.clone(),
+        }
+    }
+}
+
 impl<T, N> NodeRef<T, N> {
     pub fn from_existing(path: impl AsRef<str>, reference: Ref<Node>) -> Self {
         Self {
@@ -97,55 +220,58 @@ impl<T, N> NodeRef<T, N> {
             path: path.as_ref().to_string(),
         }
     }
-}
 
-impl<T, N> NodeRef<T, N>
-where
-    T: SubClass<Node>,
-{
-    pub fn from_parent(path: impl AsRef<str>, parent: &impl SubClass<Node>) -> Self {
-        let x = parent.upcast::<Node>();
-        let child = x.get_node(path.as_ref()).unwrap();
-
-        Self::from_existing(path, child)
+    /// The path this reference was created from.
+    pub fn path(&self) -> &str {
+        &self.path
     }
 
-    pub fn init_from_parent<P, R>(&mut self, parent: P)
-    where
-        P: AsRef<R>,
-        R: SubClass<Node>,
-    {
-        info!("Init NodeRef from parent: {}", self.path);
-        let x = parent.as_ref().upcast::<Node>();
-        let child = x.get_node(&self.path).unwrap();
+    /// Finds this reference's node under `parent`, failing with `not_found` if it isn't there.
+    fn find_in(
+        &mut self,
+        parent: TRef<Node>,
+        not_found: impl FnOnce(String) -> GuiError,
+    ) -> Result<(), GuiError> {
+        let child = parent
+            .get_node(&self.path)
+            .ok_or_else(|| not_found(self.path.clone()))?;
+
         self.reference = Some(child);
         info!("NodeRef {} init complete", self.path);
+        Ok(())
     }
 
-    pub fn resolve(&self) -> TRef<T> {
-        let r = unsafe {
-            self.reference
-                .unwrap_or_else(|| panic!("Node ref was not initialized: {}", self.path))
-                .assume_safe()
-        };
-        let r = r.cast::<T>().unwrap();
-        r
+    fn node(&self) -> Result<TRef<Node>, GuiError> {
+        let reference = self.reference.ok_or_else(|| GuiError::NotInitialized {
+            path: self.path.clone(),
+        })?;
+
+        Ok(unsafe { reference.assume_safe() })
     }
+}
 
-    /// Attempts to resolve the Node, returning None if it could not.
-    pub fn try_resolve(&self) -> Option<TRef<T>> {
-        let r = unsafe { self.reference?.assume_safe() };
-        r.cast::<T>()
+impl<T, N> NodeRef<T, N>
+where
+    T: SubClass<Node>,
+{
+    pub fn try_init_from_parent(
+        &mut self,
+        parent: TRef<impl SubClass<Node>>,
+    ) -> Result<(), GuiError> {
+        self.find_in(parent.upcast::<Node>(), |path| GuiError::NodeMissing {
+            path,
+            godot_class: short_type_name::<T>(),
+        })
     }
 
-    pub fn resolve_ref(&self) -> &T {
-        let r = unsafe {
-            self.reference
-                .unwrap_or_else(|| panic!("Node ref was not initialized: {}", self.path))
-                .assume_safe()
-        };
-        let r = r.cast::<T>().unwrap();
-        r.as_ref()
+    /// Resolves the node as a `T`.
+    pub fn try_resolve(&self) -> Result<TRef<T>, GuiError> {
+        self.node()?
+            .cast::<T>()
+            .ok_or_else(|| GuiError::WrongClass {
+                path: self.path.clone(),
+                godot_class: short_type_name::<T>(),
+            })
     }
 }
 
@@ -154,36 +280,52 @@ where
     T: NativeClass<Base = N>,
     N: SubClass<Node>,
 {
-    pub fn init_from_parent_ref(&mut self, parent: TRef<impl SubClass<Node>>) {
-        let parent = parent.upcast::<Node>();
-        let child = parent.get_node(&self.path).unwrap();
-        self.reference = Some(child);
-        info!("NodeRef {} init complete", self.path);
+    pub fn try_init_from_parent_ref(
+        &mut self,
+        parent: TRef<impl SubClass<Node>>,
+    ) -> Result<(), GuiError> {
+        self.find_in(parent.upcast::<Node>(), |path| GuiError::NodeNotFound {
+            path,
+            rust_type: short_type_name::<T>(),
+            godot_base: short_type_name::<N>(),
+        })
+    }
+
+    pub fn try_from_parent_ref(
+        path: impl AsRef<str>,
+        parent: TRef<Node>,
+    ) -> Result<Self, GuiError> {
+        let mut node_ref = Self::from_path(path);
+        node_ref.try_init_from_parent_ref(parent)?;
+        Ok(node_ref)
     }
 
-    pub fn from_parent_ref(path: impl AsRef<str>, parent: TRef<Node>) -> Self {
-        let parent_path = parent.get_path();
-        info!(
-            "Generating ref from parent: {:?} to child: {}",
-            parent_path,
-            path.as_ref()
-        );
+    /// Resolves the node's `T` script instance.
+    pub fn try_resolve_instance(&self) -> Result<RefInstance<T, Shared>, GuiError> {
+        cast_instance(&self.path, self.node()?)
+    }
 
-        let child = parent
-            .get_node(path.as_ref())
-            .expect("Child not found on parent.");
+    /// Resolves the script instance and calls `op` with it.
+    pub fn try_map<U>(&self, op: impl FnOnce(&T, TRef<N>) -> U) -> Result<U, GuiError>
+    where
+        T::UserData: Map,
+    {
+        self.try_resolve_instance()?
+            .map(op)
+            .map_err(|e| self.borrow_error(e))
+    }
 
-        Self::from_existing(path, child)
+    /// Resolves the script instance and calls `op` with it mutably.
+    pub fn try_map_mut<U>(&self, op: impl FnOnce(&mut T, TRef<N>) -> U) -> Result<U, GuiError>
+    where
+        T::UserData: MapMut,
+    {
+        self.try_resolve_instance()?
+            .map_mut(op)
+            .map_err(|e| self.borrow_error(e))
     }
 
-    pub fn resolve_instance(&self) -> RefInstance<T, Shared> {
-        let r = unsafe {
-            self.reference
-                .unwrap_or_else(|| panic!("Node ref was not initialized: {}", self.path))
-                .assume_safe()
-        };
-        let r = r.cast::<N>().unwrap();
-        let r = r.cast_instance::<T>().unwrap();
-        r
+    fn borrow_error(&self, reason: impl std::fmt::Debug) -> GuiError {
+        GuiError::borrowed::<T>(self.path.clone(), reason)
     }
 }